@@ -25,6 +25,7 @@ pub fn diff_props_object(prev_object: &Props, next_object: &Props) -> Option<Pro
                 result.insert(key.clone(), Prop::Null);
             }
             &Prop::Function(_) => (),
+            next_value if next_value.handler().is_some() => (),
             next_value => if prev_value != next_value {
                 result.insert(key.clone(), diff_props(prev_value, next_value));
             },
@@ -0,0 +1,76 @@
+use serde_json::{Map, Value};
+
+use super::super::{prop_to_json, Prop};
+
+/// what happened while dispatching an event, so hosts know whether to run the default action
+#[derive(Debug, Clone, PartialEq)]
+pub struct DispatchResult {
+    handlers: usize,
+    default_prevented: bool,
+    propagation_stopped: bool,
+    value: Prop,
+}
+
+impl DispatchResult {
+    #[inline]
+    pub fn new() -> Self {
+        DispatchResult {
+            handlers: 0,
+            default_prevented: false,
+            propagation_stopped: false,
+            value: Prop::Null,
+        }
+    }
+
+    /// the number of handlers called
+    #[inline(always)]
+    pub fn handlers(&self) -> usize {
+        self.handlers
+    }
+    #[inline(always)]
+    pub fn default_prevented(&self) -> bool {
+        self.default_prevented
+    }
+    #[inline(always)]
+    pub fn propagation_stopped(&self) -> bool {
+        self.propagation_stopped
+    }
+    /// the last value returned by a handler that was not null
+    #[inline(always)]
+    pub fn value(&self) -> &Prop {
+        &self.value
+    }
+
+    /// the result sent back to hosts
+    #[inline]
+    pub fn to_json(&self) -> Value {
+        let mut map = Map::new();
+
+        map.insert("handlers".into(), self.handlers.into());
+        map.insert("default_prevented".into(), self.default_prevented.into());
+        map.insert(
+            "propagation_stopped".into(),
+            self.propagation_stopped.into(),
+        );
+        map.insert("value".into(), prop_to_json(&self.value));
+
+        Value::Object(map)
+    }
+
+    #[inline]
+    pub(crate) fn handled(&mut self, value: Prop) {
+        self.handlers += 1;
+
+        if !value.is_null() {
+            self.value = value;
+        }
+    }
+    #[inline]
+    pub(crate) fn prevent_default(&mut self) {
+        self.default_prevented = true;
+    }
+    #[inline]
+    pub(crate) fn stop_propagation(&mut self) {
+        self.propagation_stopped = true;
+    }
+}
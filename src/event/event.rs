@@ -0,0 +1,188 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::super::{Prop, Props};
+use super::Phase;
+
+const RESERVED: [&'static str; 10] = [
+    "name",
+    "target_id",
+    "current_target_id",
+    "component_id",
+    "phase",
+    "timestamp",
+    "propagation",
+    "stop_propagation",
+    "stop_immediate_propagation",
+    "prevent_default",
+];
+
+/// a typed view of the event props handlers are called with, everything that is not a
+/// known event field is part of the detail
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    name: String,
+    target_id: String,
+    current_target_id: String,
+    phase: Phase,
+    timestamp: f64,
+    detail: Props,
+    propagation_stopped: bool,
+    immediate_propagation_stopped: bool,
+    default_prevented: bool,
+}
+
+impl Event {
+    #[inline]
+    pub fn new<N>(name: N, detail: Props) -> Self
+    where
+        N: Into<String>,
+    {
+        Event {
+            name: name.into(),
+            target_id: String::new(),
+            current_target_id: String::new(),
+            phase: Phase::Target,
+            timestamp: now(),
+            detail: detail,
+            propagation_stopped: false,
+            immediate_propagation_stopped: false,
+            default_prevented: false,
+        }
+    }
+
+    /// wraps a handler taking a typed event so it can be passed as an `on*` prop
+    #[inline]
+    pub fn handler<F>(f: F) -> Prop
+    where
+        F: 'static + Fn(&mut Event) -> Prop,
+    {
+        Prop::from(move |props: &mut Props| {
+            let mut event = Event::from_props(props);
+            let value = f(&mut event);
+            *props = event.into_props();
+            value
+        })
+    }
+
+    #[inline]
+    pub fn from_props(props: &Props) -> Self {
+        let string = |key: &str| props.get(key).string().map(Clone::clone);
+
+        Event {
+            name: string("name").unwrap_or_else(String::new),
+            target_id: string("target_id").unwrap_or_else(String::new),
+            current_target_id: string("current_target_id").unwrap_or_else(String::new),
+            phase: props
+                .get("phase")
+                .string()
+                .and_then(|phase| Phase::parse(phase))
+                .unwrap_or(Phase::Target),
+            timestamp: props.get("timestamp").number().unwrap_or_else(now),
+            detail: props
+                .iter()
+                .filter(|&(key, _)| !RESERVED.contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+            propagation_stopped: props.get("stop_propagation").is_true()
+                || props.get("propagation").is_false(),
+            immediate_propagation_stopped: props.get("stop_immediate_propagation").is_true(),
+            default_prevented: props.get("prevent_default").is_true(),
+        }
+    }
+
+    #[inline]
+    pub fn into_props(self) -> Props {
+        let mut props = self.detail;
+
+        props.set("name", self.name);
+        props.set("target_id", self.target_id);
+        props.set("current_target_id", self.current_target_id.clone());
+        props.set("component_id", self.current_target_id);
+        props.set("phase", self.phase.as_str());
+        props.set("timestamp", self.timestamp);
+
+        if self.propagation_stopped {
+            props.set("stop_propagation", true);
+        }
+        if self.immediate_propagation_stopped {
+            props.set("stop_immediate_propagation", true);
+        }
+        if self.default_prevented {
+            props.set("prevent_default", true);
+        }
+
+        props
+    }
+
+    #[inline(always)]
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+    /// the id the event was dispatched at
+    #[inline(always)]
+    pub fn target_id(&self) -> &String {
+        &self.target_id
+    }
+    /// the id of the view whose handler is running
+    #[inline(always)]
+    pub fn current_target_id(&self) -> &String {
+        &self.current_target_id
+    }
+    #[inline(always)]
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+    /// milliseconds since the unix epoch when the event was dispatched
+    #[inline(always)]
+    pub fn timestamp(&self) -> f64 {
+        self.timestamp
+    }
+    #[inline(always)]
+    pub fn detail(&self) -> &Props {
+        &self.detail
+    }
+    #[inline(always)]
+    pub fn detail_mut(&mut self) -> &mut Props {
+        &mut self.detail
+    }
+
+    /// stops the event after the handlers of the current target
+    #[inline]
+    pub fn stop_propagation(&mut self) {
+        self.propagation_stopped = true;
+    }
+    /// stops the event before any other handler
+    #[inline]
+    pub fn stop_immediate_propagation(&mut self) {
+        self.propagation_stopped = true;
+        self.immediate_propagation_stopped = true;
+    }
+    #[inline]
+    pub fn prevent_default(&mut self) {
+        self.default_prevented = true;
+    }
+
+    #[inline(always)]
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.propagation_stopped
+    }
+    #[inline(always)]
+    pub fn is_immediate_propagation_stopped(&self) -> bool {
+        self.immediate_propagation_stopped
+    }
+    #[inline(always)]
+    pub fn is_default_prevented(&self) -> bool {
+        self.default_prevented
+    }
+}
+
+/// milliseconds since the unix epoch
+#[inline]
+pub(crate) fn now() -> f64 {
+    match SystemTime::now().duration_since(UNIX_EPOCH) {
+        Ok(duration) => {
+            duration.as_secs() as f64 * 1000.0 + f64::from(duration.subsec_nanos()) / 1_000_000.0
+        }
+        Err(_) => 0.0,
+    }
+}
@@ -0,0 +1,354 @@
+use std::fmt;
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use fnv::{FnvHashMap, FnvHashSet};
+
+use super::super::{is_ancestor_id_of, parent_id, Function, Props};
+use super::event::now;
+use super::{DispatchResult, Event, Listener, Phase};
+
+#[derive(Clone)]
+pub struct EventManager(Arc<RwLock<EventManagerInner>>);
+
+unsafe impl Send for EventManager {}
+unsafe impl Sync for EventManager {}
+
+impl EventManager {
+    #[inline]
+    pub fn new() -> Self {
+        EventManager(Arc::new(RwLock::new(EventManagerInner::new())))
+    }
+
+    #[inline]
+    pub(crate) fn read(&self) -> RwLockReadGuard<EventManagerInner> {
+        self.0
+            .read()
+            .expect("failed to acquire EventManager read lock")
+    }
+
+    #[inline]
+    pub(crate) fn write(&self) -> RwLockWriteGuard<EventManagerInner> {
+        self.0
+            .write()
+            .expect("failed to acquire EventManager write lock")
+    }
+
+    /// dispatches the event named by the event's `name` prop at id, capture handlers run from
+    /// the root down to id, then handlers run from id back up to the root
+    ///
+    /// handlers can set `stop_propagation`, `stop_immediate_propagation` and
+    /// `prevent_default` to true on the event, `current_target_id` and `phase` are set
+    /// before each handler is called, once handlers are removed before they are called
+    #[inline]
+    pub fn dispatch(&self, id: &str, event: &mut Props) -> DispatchResult {
+        let mut result = DispatchResult::new();
+        let listeners = match event.get("name").string() {
+            Some(name) => self.read().listeners(id, name),
+            None => return result,
+        };
+
+        event.set("target_id", id);
+        if !event.has("timestamp") {
+            event.set("timestamp", now());
+        }
+
+        let mut stopped_at: Option<String> = None;
+
+        for (current_target_id, phase, handler) in listeners {
+            if let Some(ref stopped_at) = stopped_at {
+                // stopping propagation still runs the other handlers of the current target
+                if stopped_at != &current_target_id {
+                    break;
+                }
+            }
+
+            event.set("phase", phase.as_str());
+            event.set("current_target_id", current_target_id.clone());
+            event.set("component_id", current_target_id.clone());
+
+            if handler.listener.once() {
+                self.write().remove_once(&current_target_id, &handler.key);
+            }
+
+            result.handled((&*handler.func)(event));
+
+            if event.get("stop_immediate_propagation").is_true() {
+                result.stop_propagation();
+                break;
+            }
+            if event.get("stop_propagation").is_true() || event.get("propagation").is_false() {
+                result.stop_propagation();
+                stopped_at = Some(current_target_id);
+            }
+        }
+
+        if event.get("prevent_default").is_true() {
+            result.prevent_default();
+        }
+
+        result
+    }
+
+    /// dispatches a typed event, see `dispatch`
+    #[inline]
+    pub fn dispatch_event(&self, id: &str, event: &mut Event) -> DispatchResult {
+        let mut props = event.clone().into_props();
+        let result = self.dispatch(id, &mut props);
+        *event = Event::from_props(&props);
+        result
+    }
+}
+
+impl fmt::Debug for EventManager {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:?}",
+            self.read()
+                .events
+                .iter()
+                .map(|(k, v)| (
+                    k.clone(),
+                    v.iter()
+                        .map(|(i, _)| i.clone())
+                        .collect::<FnvHashSet<String>>()
+                ))
+                .collect::<FnvHashMap<String, FnvHashSet<String>>>()
+        )
+    }
+}
+
+#[derive(Clone)]
+struct Handler {
+    key: String,
+    listener: Listener,
+    func: Arc<Function>,
+}
+
+pub(crate) struct EventManagerInner {
+    /// event names to view ids to the handlers of the props listening for the event
+    events: FnvHashMap<String, FnvHashMap<String, Vec<Handler>>>,
+    /// portal target container ids to the ids of the portals rendered into them, in the order
+    /// their fragments are in the container
+    portals: FnvHashMap<String, Vec<String>>,
+    /// the ids and prop names of the once handlers removed since the host was last told
+    removed_once: Vec<(String, String)>,
+}
+
+impl EventManagerInner {
+    #[inline]
+    fn new() -> Self {
+        EventManagerInner {
+            events: FnvHashMap::default(),
+            portals: FnvHashMap::default(),
+            removed_once: Vec::new(),
+        }
+    }
+    /// adds the handler of the prop named key at id, replacing any handler it had
+    #[inline]
+    pub(crate) fn add(&mut self, id: &str, key: &str, listener: Listener, func: Arc<Function>) {
+        let handlers = self
+            .events
+            .entry(listener.name().clone())
+            .or_insert_with(FnvHashMap::default)
+            .entry(id.into())
+            .or_insert_with(Vec::new);
+
+        handlers.retain(|handler| handler.key != key);
+        handlers.push(Handler {
+            key: key.into(),
+            listener: listener,
+            func: func,
+        });
+
+        // the host listens again, so it is not told the called once handler is gone
+        self.removed_once
+            .retain(|&(ref removed_id, ref removed_key)| removed_id != id || removed_key != key);
+    }
+
+    /// swaps the handler of the prop named key at id for func, returns false if func is
+    /// already the handler or the prop has no handler, like a once handler that was called
+    #[inline]
+    pub(crate) fn replace(&mut self, id: &str, key: &str, func: Arc<Function>) -> bool {
+        let name = Listener::from_key(key).name().clone();
+
+        match self
+            .events
+            .get_mut(&name)
+            .and_then(|handlers| handlers.get_mut(id))
+            .and_then(|handlers| handlers.iter_mut().find(|handler| handler.key == key))
+        {
+            Some(ref mut handler) if !Arc::ptr_eq(&handler.func, &func) => {
+                handler.func = func;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn remove(&mut self, id: &str, key: &str) {
+        let name = Listener::from_key(key).name().clone();
+
+        let remove = if let Some(handlers) = self.events.get_mut(&name) {
+            let remove_id = if let Some(id_handlers) = handlers.get_mut(id) {
+                id_handlers.retain(|handler| handler.key != key);
+                id_handlers.is_empty()
+            } else {
+                false
+            };
+
+            if remove_id {
+                handlers.remove(id);
+            }
+            handlers.is_empty()
+        } else {
+            false
+        };
+
+        if remove {
+            self.events.remove(&name);
+        }
+    }
+
+    /// removes a once handler after it is called, the host is told in the next transaction
+    #[inline]
+    pub(crate) fn remove_once(&mut self, id: &str, key: &str) {
+        self.remove(id, key);
+        self.removed_once.push((id.into(), key.into()));
+    }
+
+    #[inline]
+    pub(crate) fn has_removed_once(&self, id: &str) -> bool {
+        self.removed_once
+            .iter()
+            .any(|&(ref removed_id, _)| is_ancestor_id_of(id, removed_id))
+    }
+
+    /// takes the ids and prop names of the once handlers removed at or below id
+    #[inline]
+    pub(crate) fn take_removed_once(&mut self, id: &str) -> Vec<(String, String)> {
+        let mut taken = Vec::new();
+
+        self.removed_once.retain(|removed| {
+            if is_ancestor_id_of(id, &removed.0) {
+                taken.push(removed.clone());
+                false
+            } else {
+                true
+            }
+        });
+
+        taken
+    }
+
+    /// removes every handler at or below id, returning the removed ids and prop names
+    #[inline]
+    pub(crate) fn remove_subtree(&mut self, id: &str) -> Vec<(String, String)> {
+        let mut removed = Vec::new();
+
+        for handlers in self.events.values_mut() {
+            handlers.retain(|handlers_id, handlers| {
+                if is_ancestor_id_of(id, handlers_id) {
+                    for handler in handlers.iter() {
+                        removed.push((handlers_id.clone(), handler.key.clone()));
+                    }
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+        self.events.retain(|_, handlers| !handlers.is_empty());
+
+        removed
+    }
+
+    /// adds the portal at id to the target container, returning the index of its fragment in
+    /// the container
+    #[inline]
+    pub(crate) fn add_portal(&mut self, target: &str, id: &str) -> usize {
+        let portals = self.portals.entry(target.into()).or_insert_with(Vec::new);
+        portals.push(id.into());
+        portals.len() - 1
+    }
+
+    #[inline]
+    pub(crate) fn remove_portal(&mut self, target: &str, id: &str) {
+        let remove = if let Some(portals) = self.portals.get_mut(target) {
+            portals.retain(|portal_id| portal_id != id);
+            portals.is_empty()
+        } else {
+            false
+        };
+
+        if remove {
+            self.portals.remove(target);
+        }
+    }
+
+    /// removes the portals rendered at or below id
+    #[inline]
+    pub(crate) fn remove_portals(&mut self, id: &str) {
+        for portals in self.portals.values_mut() {
+            portals.retain(|portal_id| !is_ancestor_id_of(id, portal_id));
+        }
+        self.portals.retain(|_, portals| !portals.is_empty());
+    }
+
+    /// the ids from id up to the root, portals render their children below their own id so
+    /// events bubble through the portal's logical parents
+    #[inline]
+    fn path(&self, id: &str) -> Vec<String> {
+        let mut path = Vec::new();
+        let mut id = id.to_owned();
+
+        while !id.is_empty() {
+            let next_id = parent_id(&id);
+            path.push(id);
+            id = next_id;
+        }
+
+        path
+    }
+
+    /// the handlers for the event in the order they are called
+    #[inline]
+    fn listeners(&self, id: &str, name: &str) -> Vec<(String, Phase, Handler)> {
+        let mut listeners = Vec::new();
+        let handlers = match self.events.get(name) {
+            Some(handlers) => handlers,
+            None => return listeners,
+        };
+        let path = self.path(id);
+
+        for current_id in path.iter().rev() {
+            if let Some(id_handlers) = handlers.get(current_id) {
+                let phase = if current_id == id {
+                    Phase::Target
+                } else {
+                    Phase::Capture
+                };
+
+                for handler in id_handlers.iter().filter(|h| h.listener.capture()) {
+                    listeners.push((current_id.clone(), phase, handler.clone()));
+                }
+            }
+        }
+        for current_id in path.iter() {
+            if let Some(id_handlers) = handlers.get(current_id) {
+                let phase = if current_id == id {
+                    Phase::Target
+                } else {
+                    Phase::Bubble
+                };
+
+                for handler in id_handlers.iter().filter(|h| !h.listener.capture()) {
+                    listeners.push((current_id.clone(), phase, handler.clone()));
+                }
+            }
+        }
+
+        listeners
+    }
+}
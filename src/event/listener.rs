@@ -0,0 +1,109 @@
+use std::sync::Arc;
+
+use super::super::{Function, Prop};
+
+const OPTIONS: [&'static str; 3] = ["capture", "passive", "once"];
+
+/// how a host should listen for an `on*` prop, sent in the transaction listeners map
+///
+/// options are set with suffixes on the prop name like `onclick_once` or
+/// `onscroll_passive_capture`, or with an object prop like
+/// `{ "handler": f, "passive": true }`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Listener {
+    name: String,
+    capture: bool,
+    passive: bool,
+    once: bool,
+}
+
+impl Listener {
+    #[inline]
+    pub fn new<N>(name: N) -> Self
+    where
+        N: Into<String>,
+    {
+        Listener {
+            name: name.into(),
+            capture: false,
+            passive: false,
+            once: false,
+        }
+    }
+
+    /// the listener for the prop name, without the options of an object prop
+    #[inline]
+    pub fn from_key(key: &str) -> Self {
+        let mut name = key;
+        let mut listener = Listener::new("");
+
+        'suffixes: loop {
+            for option in OPTIONS.iter() {
+                let suffix = format!("_{}", option);
+
+                if name.len() > suffix.len() && name.ends_with(&suffix) {
+                    listener.set(option, true);
+                    name = &name[..name.len() - suffix.len()];
+                    continue 'suffixes;
+                }
+            }
+            break;
+        }
+
+        listener.name = name.into();
+        listener
+    }
+
+    /// the listener and handler of an `on*` prop, `None` if the prop is not a handler
+    #[inline]
+    pub fn from_prop(key: &str, value: &Prop) -> Option<(Self, Arc<Function>)> {
+        if !key.starts_with("on") {
+            return None;
+        }
+
+        let func = match value.handler() {
+            Some(func) => func.clone(),
+            None => return None,
+        };
+        let mut listener = Listener::from_key(key);
+
+        if let Some(object) = value.object() {
+            for option in OPTIONS.iter() {
+                if object.get(option).is_true() {
+                    listener.set(option, true);
+                }
+            }
+        }
+
+        Some((listener, func))
+    }
+
+    /// the event name handlers are dispatched for, `onclick` for `onclick_once`
+    #[inline(always)]
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+    #[inline(always)]
+    pub fn capture(&self) -> bool {
+        self.capture
+    }
+    #[inline(always)]
+    pub fn passive(&self) -> bool {
+        self.passive
+    }
+    /// once listeners are removed after they are first called
+    #[inline(always)]
+    pub fn once(&self) -> bool {
+        self.once
+    }
+
+    #[inline]
+    fn set(&mut self, option: &str, value: bool) {
+        match option {
+            "capture" => self.capture = value,
+            "passive" => self.passive = value,
+            "once" => self.once = value,
+            _ => (),
+        }
+    }
+}
@@ -0,0 +1,11 @@
+mod dispatch_result;
+mod event;
+mod event_manager;
+mod listener;
+mod phase;
+
+pub use self::dispatch_result::DispatchResult;
+pub use self::event::Event;
+pub use self::event_manager::EventManager;
+pub use self::listener::Listener;
+pub use self::phase::Phase;
@@ -0,0 +1,28 @@
+/// the phase of an event dispatch, handlers named `on<name>_capture` run in the capture phase
+/// on the way down from the root, the rest run in the bubble phase on the way back up
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    Capture,
+    Target,
+    Bubble,
+}
+
+impl Phase {
+    #[inline]
+    pub fn parse(phase: &str) -> Option<Self> {
+        match phase {
+            "capture" => Some(Phase::Capture),
+            "target" => Some(Phase::Target),
+            "bubble" => Some(Phase::Bubble),
+            _ => None,
+        }
+    }
+    #[inline]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            &Phase::Capture => "capture",
+            &Phase::Target => "target",
+            &Phase::Bubble => "bubble",
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::error::Error;
+use std::fmt;
+
+/// the error returned when a transaction does not match the views a `RawHost` holds
+#[derive(Debug, Clone, PartialEq)]
+pub enum ApplyError {
+    /// no view with the id
+    NotFound(String),
+    /// the view at the id is text so it has no props or children
+    NotData(String),
+    /// the index is out of bounds of the children at the id
+    Index(String, usize),
+    /// an order at the id inserts a key it did not remove
+    Key(String, String),
+}
+
+impl ApplyError {
+    /// the view id the transaction failed at
+    #[inline]
+    pub fn id(&self) -> &String {
+        match self {
+            &ApplyError::NotFound(ref id) => id,
+            &ApplyError::NotData(ref id) => id,
+            &ApplyError::Index(ref id, _) => id,
+            &ApplyError::Key(ref id, _) => id,
+        }
+    }
+}
+
+impl fmt::Display for ApplyError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            &ApplyError::NotFound(ref id) => write!(f, "no view at {}", id),
+            &ApplyError::NotData(ref id) => write!(f, "view at {} is text", id),
+            &ApplyError::Index(ref id, index) => {
+                write!(f, "index {} out of bounds of children at {}", index, id)
+            }
+            &ApplyError::Key(ref id, ref key) => {
+                write!(f, "order at {} inserts key {} it did not remove", id, key)
+            }
+        }
+    }
+}
+
+impl Error for ApplyError {
+    #[inline]
+    fn description(&self) -> &str {
+        match self {
+            &ApplyError::NotFound(_) => "view not found",
+            &ApplyError::NotData(_) => "view is text",
+            &ApplyError::Index(_, _) => "index out of bounds",
+            &ApplyError::Key(_, _) => "key not removed",
+        }
+    }
+}
@@ -0,0 +1,5 @@
+mod apply_error;
+mod raw_host;
+
+pub use self::apply_error::ApplyError;
+pub use self::raw_host::RawHost;
@@ -0,0 +1,331 @@
+use fnv::FnvHashMap;
+use serde_json::{Map, Value};
+
+use super::super::{parent_id, view_id, Listener, Order, Patch, RawView, Transaction};
+use super::ApplyError;
+
+#[derive(Debug, Clone, PartialEq)]
+enum HostView {
+    Text(String),
+    Data {
+        kind: String,
+        key: Option<String>,
+        props: Map<String, Value>,
+        children: Vec<String>,
+    },
+    Fragment(Vec<String>),
+}
+
+/// an in memory host that applies transactions to raw views, it is the reference for how
+/// hosts should handle the transactions a renderer sends
+///
+/// views are stored by id, containers are the host ids views are mounted or portaled into,
+/// fragments keep the ids of their children so they can be patched as one
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RawHost {
+    containers: FnvHashMap<String, Vec<String>>,
+    views: FnvHashMap<String, HostView>,
+    events: FnvHashMap<String, FnvHashMap<String, Listener>>,
+}
+
+impl RawHost {
+    #[inline]
+    pub fn new() -> Self {
+        RawHost {
+            containers: FnvHashMap::default(),
+            views: FnvHashMap::default(),
+            events: FnvHashMap::default(),
+        }
+    }
+
+    /// applies the patches, ancestors first, then the removes and last the events, the host
+    /// is left partially updated if an error is returned
+    #[inline]
+    pub fn apply(&mut self, transaction: &Transaction) -> Result<(), ApplyError> {
+        let mut ids = transaction.patches().keys().collect::<Vec<&String>>();
+        ids.sort_by_key(|id| (id.matches('.').count(), *id));
+
+        for id in ids {
+            for patch in &transaction.patches()[id] {
+                self.apply_patch(id, patch)?;
+            }
+        }
+
+        for id in transaction.removes().keys() {
+            self.remove(id)?;
+        }
+
+        for (id, events) in transaction.events() {
+            for (name, added) in events {
+                if *added {
+                    self.events
+                        .entry(id.clone())
+                        .or_insert_with(FnvHashMap::default)
+                        .insert(name.clone(), transaction.listener(id, name));
+                } else {
+                    let remove = if let Some(names) = self.events.get_mut(id) {
+                        names.remove(name);
+                        names.is_empty()
+                    } else {
+                        false
+                    };
+
+                    if remove {
+                        self.events.remove(id);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// the view at id
+    #[inline]
+    pub fn view(&self, id: &str) -> Option<RawView> {
+        self.views.get(id).map(|view| match view {
+            &HostView::Text(ref text) => RawView::Text(text.clone()),
+            &HostView::Data {
+                ref kind,
+                ref key,
+                ref props,
+                ref children,
+            } => RawView::Data {
+                kind: kind.clone(),
+                key: key.clone(),
+                props: props.clone(),
+                children: children
+                    .iter()
+                    .filter_map(|child_id| self.view(child_id))
+                    .collect(),
+            },
+            &HostView::Fragment(ref children) => RawView::Fragment(
+                children
+                    .iter()
+                    .filter_map(|child_id| self.view(child_id))
+                    .collect(),
+            ),
+        })
+    }
+
+    /// the views mounted or portaled into the container
+    #[inline]
+    pub fn container(&self, id: &str) -> Option<Vec<RawView>> {
+        self.containers
+            .get(id)
+            .map(|ids| ids.iter().filter_map(|id| self.view(id)).collect())
+    }
+
+    #[inline]
+    pub fn has_event(&self, id: &str, name: &str) -> bool {
+        self.listener(id, name).is_some()
+    }
+    #[inline]
+    pub fn listener(&self, id: &str, name: &str) -> Option<&Listener> {
+        self.events.get(id).and_then(|names| names.get(name))
+    }
+    /// the listeners by view id and prop name
+    #[inline]
+    pub fn events(&self) -> &FnvHashMap<String, FnvHashMap<String, Listener>> {
+        &self.events
+    }
+
+    #[inline]
+    fn apply_patch(&mut self, id: &str, patch: &Patch) -> Result<(), ApplyError> {
+        match patch {
+            &Patch::Mount(ref view) => {
+                self.remove_views(id);
+                self.add(id, view);
+                self.containers.insert(id.into(), vec![id.into()]);
+                Ok(())
+            }
+            &Patch::Insert(ref child_id, index, ref view) => {
+                if !self.views.contains_key(id) && !self.containers.contains_key(id) {
+                    // portals insert into containers the renderer never mounted
+                    self.containers.insert(id.into(), Vec::new());
+                }
+                if self.views.contains_key(child_id) {
+                    // a view inserted with the id of a mounted view moves it
+                    self.remove(child_id)?;
+                }
+                let children = self.children_mut(id)?;
+
+                if index > children.len() {
+                    return Err(ApplyError::Index(id.into(), index));
+                }
+                children.insert(index, child_id.clone());
+
+                self.add(child_id, view);
+                Ok(())
+            }
+            &Patch::Replace(_, ref next_view) => {
+                if !self.views.contains_key(id) {
+                    return Err(ApplyError::NotFound(id.into()));
+                }
+                self.remove_views(id);
+                self.add(id, next_view);
+                Ok(())
+            }
+            &Patch::Order(ref order) => self.order(id, order),
+            &Patch::Props(_, ref diff_props) => match self.views.get_mut(id) {
+                Some(&mut HostView::Data { ref mut props, .. }) => {
+                    merge_props(props, diff_props);
+                    Ok(())
+                }
+                Some(_) => Err(ApplyError::NotData(id.into())),
+                None => Err(ApplyError::NotFound(id.into())),
+            },
+        }
+    }
+
+    /// removes the keyed and deleted children, then inserts the keyed children at their
+    /// next index
+    #[inline]
+    fn order(&mut self, id: &str, order: &Order) -> Result<(), ApplyError> {
+        let children = self.children_mut(id)?;
+        let mut keyed = FnvHashMap::default();
+
+        for &(index, ref key) in order.removes() {
+            if index >= children.len() {
+                return Err(ApplyError::Index(id.into(), index));
+            }
+            let child_id = children.remove(index);
+
+            if let &Some(ref key) = key {
+                keyed.insert(key.clone(), child_id);
+            }
+        }
+
+        for &(ref key, index) in order.inserts() {
+            // only keyed children are moved
+            let key = match key {
+                &Some(ref key) => key,
+                &None => continue,
+            };
+            let child_id = keyed
+                .remove(key)
+                .ok_or_else(|| ApplyError::Key(id.into(), key.clone()))?;
+
+            let index = if index > children.len() {
+                children.len()
+            } else {
+                index
+            };
+            children.insert(index, child_id);
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn remove(&mut self, id: &str) -> Result<(), ApplyError> {
+        if !self.views.contains_key(id) {
+            return Err(ApplyError::NotFound(id.into()));
+        }
+
+        let parent_id = parent_id(id);
+
+        if let Ok(children) = self.children_mut(&parent_id) {
+            children.retain(|child_id| child_id != id);
+        }
+
+        // portal fragments are in the container of their target, mounted roots in their own
+        for children in self.containers.values_mut() {
+            children.retain(|child_id| child_id != id);
+        }
+        let remove_container = self.containers.get(id).map(Vec::is_empty).unwrap_or(false);
+
+        if remove_container {
+            self.containers.remove(id);
+        }
+
+        self.remove_views(id);
+
+        Ok(())
+    }
+
+    #[inline]
+    fn children_mut(&mut self, id: &str) -> Result<&mut Vec<String>, ApplyError> {
+        if let Some(view) = self.views.get_mut(id) {
+            match view {
+                &mut HostView::Data {
+                    ref mut children, ..
+                } => Ok(children),
+                &mut HostView::Fragment(ref mut children) => Ok(children),
+                &mut HostView::Text(_) => Err(ApplyError::NotData(id.into())),
+            }
+        } else if let Some(children) = self.containers.get_mut(id) {
+            Ok(children)
+        } else {
+            Err(ApplyError::NotFound(id.into()))
+        }
+    }
+
+    #[inline]
+    fn add(&mut self, id: &str, view: &RawView) {
+        let view = match view {
+            &RawView::Text(ref text) => HostView::Text(text.clone()),
+            &RawView::Data {
+                ref kind,
+                ref key,
+                ref props,
+                ref children,
+            } => HostView::Data {
+                kind: kind.clone(),
+                key: key.clone(),
+                props: props.clone(),
+                children: self.add_children(id, children),
+            },
+            &RawView::Fragment(ref children) => HostView::Fragment(self.add_children(id, children)),
+        };
+
+        self.views.insert(id.into(), view);
+    }
+
+    #[inline]
+    fn add_children(&mut self, id: &str, children: &Vec<RawView>) -> Vec<String> {
+        children
+            .iter()
+            .enumerate()
+            .map(|(index, child)| {
+                let child_id = view_id(id, child.key(), index);
+                self.add(&child_id, child);
+                child_id
+            })
+            .collect()
+    }
+
+    #[inline]
+    fn remove_views(&mut self, id: &str) {
+        match self.views.remove(id) {
+            Some(HostView::Data { children, .. }) | Some(HostView::Fragment(children)) => {
+                for child_id in children {
+                    self.remove_views(&child_id);
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// merges a props diff, null values are removed and objects are merged
+#[inline]
+fn merge_props(props: &mut Map<String, Value>, diff_props: &Map<String, Value>) {
+    for (key, value) in diff_props {
+        match value {
+            &Value::Null => {
+                props.remove(key);
+            }
+            &Value::Object(ref diff_object) => {
+                if let Some(&mut Value::Object(ref mut object)) = props.get_mut(key) {
+                    merge_props(object, diff_object);
+                    continue;
+                }
+                props.insert(key.clone(), value.clone());
+            }
+            value => {
+                props.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
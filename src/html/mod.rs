@@ -0,0 +1,5 @@
+mod escape;
+mod render;
+
+pub use self::escape::{escape_attribute, escape_text};
+pub use self::render::{render_to_raw_view, render_to_string, render_to_writer};
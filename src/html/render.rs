@@ -0,0 +1,292 @@
+use std::io::{self, Write};
+
+use messenger;
+use serde_json::{Map, Number, Value};
+
+use super::super::renderer::{catch_render_error, NodeInner};
+use super::super::view::flatten_children;
+use super::super::{
+    props_to_json, view_id, Children, EventManager, Instance, Props, RawView, Renderer,
+    TypedContext, Updater, View,
+};
+use super::{escape_attribute, escape_text};
+
+/// the largest integer every f64 below it can represent
+const MAX_SAFE_INTEGER: f64 = 9007199254740992.0;
+
+static VOID_ELEMENTS: [&'static str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+/// resolves every component in the view and returns the host level tree
+#[inline]
+pub fn render_to_raw_view(view: View) -> RawView {
+    let (server, _, _) = messenger::unbounded_channel();
+    let renderer = Renderer::new_empty(EventManager::new(), server);
+    let root_id = renderer.root_id().clone();
+
+    resolve(
+        &renderer,
+        root_id,
+        0,
+        view,
+        &Props::new(),
+        &TypedContext::new(),
+    )
+}
+
+/// resolves every component in the view and serializes it as html, panics if the view
+/// has an invalid tag name
+#[inline]
+pub fn render_to_string(view: View) -> String {
+    let mut out = Vec::new();
+    render_to_writer(view, &mut out).expect("failed to render html");
+    String::from_utf8(out).expect("rendered html is not valid utf-8")
+}
+
+/// resolves every component in the view and writes it as html to the writer, invalid tag
+/// names are an `InvalidInput` error and attributes with invalid names are skipped
+#[inline]
+pub fn render_to_writer<W>(view: View, writer: &mut W) -> io::Result<()>
+where
+    W: Write,
+{
+    write_raw_view(&render_to_raw_view(view), writer)
+}
+
+#[inline]
+fn resolve(
+    renderer: &Renderer,
+    id: String,
+    depth: usize,
+    view: View,
+    parent_context: &Props,
+    parent_typed_context: &TypedContext,
+) -> RawView {
+    if let Some(component) = view.component().map(Clone::clone) {
+        let mut context = component.context(view.props().unwrap());
+        context = component.inherit_context(context, parent_context);
+
+        let mut typed_context = component.typed_context(view.props().unwrap());
+        typed_context = component.inherit_typed_context(typed_context, parent_typed_context);
+
+        let state = component.initial_state(view.props().unwrap());
+        let updater = Updater::new(id.clone(), depth, renderer.clone());
+        let instance = Instance::new(state, context, typed_context, updater);
+
+        let rendered_view = NodeInner::render_component_view(&instance, &view, &component);
+
+        // errors below the component render its fallback, like they do in the renderer
+        match catch_render_error(|| {
+            resolve(
+                renderer,
+                id.clone(),
+                depth + 1,
+                rendered_view,
+                &instance.context,
+                &instance.typed_context,
+            )
+        }) {
+            Ok(raw_view) => raw_view,
+            Err(error) => resolve(
+                renderer,
+                id,
+                depth + 1,
+                NodeInner::catch_error(&instance, &view, &component, error),
+                &instance.context,
+                &instance.typed_context,
+            ),
+        }
+    } else if view.portal_target().is_some() {
+        // portals render into another container, so only their placeholder is part of the page
+        RawView::Text(String::new())
+    } else {
+        match view {
+            View::Text(text) => RawView::Text(text),
+            View::Data {
+                kind,
+                key,
+                props,
+                children,
+            } => RawView::Data {
+                kind: kind.take_string(),
+                key: key,
+                props: props_to_json(&props),
+                children: resolve_children(
+                    renderer,
+                    &id,
+                    children,
+                    parent_context,
+                    parent_typed_context,
+                ),
+            },
+            View::Fragment(children) => RawView::Fragment(resolve_children(
+                renderer,
+                &id,
+                children,
+                parent_context,
+                parent_typed_context,
+            )),
+        }
+    }
+}
+
+#[inline]
+fn resolve_children(
+    renderer: &Renderer,
+    id: &str,
+    mut children: Children,
+    parent_context: &Props,
+    parent_typed_context: &TypedContext,
+) -> Vec<RawView> {
+    flatten_children(&mut children);
+
+    children
+        .into_iter()
+        .enumerate()
+        .map(|(index, child)| {
+            let child_id = view_id(id, child.key(), index);
+            resolve(
+                renderer,
+                child_id,
+                0,
+                child,
+                parent_context,
+                parent_typed_context,
+            )
+        })
+        .collect()
+}
+
+#[inline]
+fn write_raw_view<W>(view: &RawView, writer: &mut W) -> io::Result<()>
+where
+    W: Write,
+{
+    match view {
+        &RawView::Text(ref text) => writer.write_all(escape_text(text).as_bytes()),
+        &RawView::Fragment(ref children) => {
+            for child in children {
+                write_raw_view(child, writer)?;
+            }
+            Ok(())
+        }
+        &RawView::Data {
+            ref kind,
+            ref props,
+            ref children,
+            ..
+        } => {
+            if !is_valid_tag_name(kind) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid tag name {:?}", kind),
+                ));
+            }
+
+            write!(writer, "<{}", kind)?;
+
+            for (key, value) in props {
+                write_attribute(key, value, writer)?;
+            }
+
+            writer.write_all(b">")?;
+
+            if children.is_empty() && VOID_ELEMENTS.contains(&kind.as_str()) {
+                return Ok(());
+            }
+
+            for child in children {
+                write_raw_view(child, writer)?;
+            }
+
+            write!(writer, "</{}>", kind)
+        }
+    }
+}
+
+#[inline]
+fn write_attribute<W>(key: &str, value: &Value, writer: &mut W) -> io::Result<()>
+where
+    W: Write,
+{
+    if !is_valid_attribute_name(key) {
+        return Ok(());
+    }
+
+    match value {
+        &Value::Null | &Value::Bool(false) => Ok(()),
+        &Value::Bool(true) => write!(writer, " {}", key),
+        &Value::String(ref string) => write!(writer, " {}=\"{}\"", key, escape_attribute(string)),
+        &Value::Number(ref number) => write!(writer, " {}=\"{}\"", key, number_to_string(number)),
+        &Value::Object(ref object) if key == "style" => {
+            write!(
+                writer,
+                " {}=\"{}\"",
+                key,
+                escape_attribute(&style_to_css(object))
+            )
+        }
+        other => write!(
+            writer,
+            " {}=\"{}\"",
+            key,
+            escape_attribute(&other.to_string())
+        ),
+    }
+}
+
+#[inline]
+fn style_to_css(style: &Map<String, Value>) -> String {
+    let mut css = String::new();
+
+    for (key, value) in style {
+        css.push_str(key);
+        css.push(':');
+        match value {
+            &Value::String(ref string) => css.push_str(string),
+            &Value::Number(ref number) => css.push_str(&number_to_string(number)),
+            other => css.push_str(&other.to_string()),
+        }
+        css.push(';');
+    }
+
+    css
+}
+
+/// props hold numbers as f64, whole numbers are written without a fraction
+#[inline]
+fn number_to_string(number: &Number) -> String {
+    match number.as_f64() {
+        Some(float) if float.fract() == 0.0 && float.abs() < MAX_SAFE_INTEGER => {
+            (float as i64).to_string()
+        }
+        _ => number.to_string(),
+    }
+}
+
+/// tag names start with a letter and contain only letters, digits, `-`, `_`, `.` and `:`
+#[inline]
+fn is_valid_tag_name(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(first) if first.is_ascii_alphabetic() => chars.all(|ch| match ch {
+            '-' | '_' | '.' | ':' => true,
+            ch => ch.is_ascii_alphanumeric(),
+        }),
+        _ => false,
+    }
+}
+
+/// attribute names can not be empty or contain whitespace, quotes, `<`, `>`, `/`, `=` or
+/// control characters
+#[inline]
+fn is_valid_attribute_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().all(|ch| match ch {
+            '"' | '\'' | '<' | '>' | '/' | '=' => false,
+            ch => !ch.is_whitespace() && !ch.is_control(),
+        })
+}
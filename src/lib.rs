@@ -1,4 +1,5 @@
 extern crate fnv;
+extern crate futures;
 extern crate messenger;
 extern crate serde;
 #[macro_use]
@@ -6,24 +7,40 @@ extern crate serde_derive;
 extern crate serde_json;
 
 mod diff;
+mod event;
+mod host;
+mod html;
 #[macro_use]
 mod prop;
 mod renderer;
+mod scheduler;
+#[cfg(feature = "test-renderer")]
+mod test_renderer;
 mod transaction;
 mod utils;
 #[macro_use]
 pub mod view;
-mod event_manager;
 
 pub use self::diff::{diff_children, diff_props, diff_props_object, DiffChildren};
-pub use self::event_manager::EventManager;
+pub use self::event::{DispatchResult, Event, EventManager, Listener, Phase};
+pub use self::host::{ApplyError, RawHost};
+pub use self::html::{
+  escape_attribute, escape_text, render_to_raw_view, render_to_string, render_to_writer,
+};
 pub use self::prop::{
   array_to_json, prop_to_json, props_to_json, Array, Function, Number, Prop, Props,
 };
-pub use self::renderer::{Instance, Renderer, Updater};
-pub use self::transaction::{Order, Patch, RawView, Transaction};
+pub use self::renderer::{
+  Instance, LoadState, Ref, Renderer, Request, RpcError, SetState, SetStateAsync, Updater,
+};
+pub use self::scheduler::{ManualClock, Scheduler, Task, ThreadScheduler};
+#[cfg(feature = "test-renderer")]
+pub use self::test_renderer::TestRenderer;
+pub use self::transaction::{Mismatch, Order, Patch, RawView, Transaction};
 pub use self::utils::{
   child_view_id, is_ancestor_id_of, is_boundary, next_descendant_id, parent_id, traverse_path,
   view_id,
 };
-pub use self::view::{Children, Component, View, ViewKind};
+pub use self::view::{
+  memo, Children, Component, PureComponent, RenderError, TypedContext, View, ViewKind,
+};
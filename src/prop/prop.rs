@@ -57,6 +57,15 @@ impl Prop {
             _ => None,
         }
     }
+    /// the function of a function prop or of the `handler` of an object prop
+    #[inline]
+    pub fn handler(&self) -> Option<&Arc<Function>> {
+        match self {
+            &Prop::Function(ref v) => Some(v),
+            &Prop::Object(ref v) => v.get("handler").function(),
+            _ => None,
+        }
+    }
     #[inline]
     pub fn array(&self) -> Option<&Array> {
         match self {
@@ -582,6 +591,8 @@ pub fn props_to_json(props: &Props) -> Map<String, Value> {
             &Prop::Array(ref v) => {
                 out.insert(k.clone(), Value::Array(array_to_json(v)));
             }
+            // handler objects are listeners like functions
+            &Prop::Object(ref v) if v.get("handler").is_function() => (),
             &Prop::Object(ref v) => {
                 out.insert(k.clone(), Value::Object(props_to_json(v)));
             }
@@ -0,0 +1,179 @@
+use std::any::Any;
+use std::mem;
+
+use super::super::Prop;
+
+pub type Cleanup = Box<dyn FnOnce()>;
+pub type Effect = Box<dyn FnOnce() -> Cleanup>;
+
+enum Hook {
+    State(Prop),
+    Effect {
+        deps: Prop,
+        effect: Option<Effect>,
+        cleanup: Option<Cleanup>,
+    },
+    Memo {
+        deps: Prop,
+        value: Box<dyn Any>,
+    },
+}
+
+/// the hook slots of an instance in the order they are used while rendering
+pub struct Hooks {
+    index: usize,
+    hooks: Vec<Hook>,
+}
+
+impl Hooks {
+    #[inline]
+    pub fn new() -> Self {
+        Hooks {
+            index: 0,
+            hooks: Vec::new(),
+        }
+    }
+
+    /// called before each render so hooks get the slots they had last render
+    #[inline]
+    pub fn start_render(&mut self) {
+        self.index = 0;
+    }
+
+    /// the value of a state key that is not set yet, init is only used the first render
+    #[inline]
+    pub fn state(&mut self, init: Prop) -> Prop {
+        let index = self.next_index();
+
+        if let Some(&Hook::State(ref value)) = self.hooks.get(index) {
+            return value.clone();
+        }
+
+        self.set(index, Hook::State(init.clone()));
+        init
+    }
+
+    /// queues the effect if this is the first render, deps is null or deps changed
+    #[inline]
+    pub fn effect(&mut self, deps: Prop, effect: Effect) {
+        let index = self.next_index();
+
+        if let Some(&mut Hook::Effect {
+            deps: ref mut prev_deps,
+            effect: ref mut prev_effect,
+            ..
+        }) = self.hooks.get_mut(index)
+        {
+            if deps.is_null() || prev_deps != &deps {
+                *prev_deps = deps;
+                *prev_effect = Some(effect);
+            }
+            return;
+        }
+
+        self.set(
+            index,
+            Hook::Effect {
+                deps: deps,
+                effect: Some(effect),
+                cleanup: None,
+            },
+        );
+    }
+
+    /// the value memoized with deps and the slot index, `None` if this is the first render,
+    /// deps is null or deps changed
+    #[inline]
+    pub fn memo<T>(&mut self, deps: &Prop) -> (usize, Option<T>)
+    where
+        T: 'static + Clone,
+    {
+        let index = self.next_index();
+
+        if let Some(&Hook::Memo {
+            deps: ref prev_deps,
+            ref value,
+        }) = self.hooks.get(index)
+        {
+            if !deps.is_null() && prev_deps == deps {
+                return (index, value.downcast_ref::<T>().map(Clone::clone));
+            }
+        }
+
+        (index, None)
+    }
+
+    #[inline]
+    pub fn set_memo<T>(&mut self, index: usize, deps: Prop, value: T)
+    where
+        T: 'static,
+    {
+        self.set(
+            index,
+            Hook::Memo {
+                deps: deps,
+                value: Box::new(value),
+            },
+        );
+    }
+
+    /// takes the queued effects with the cleanups they replace
+    #[inline]
+    pub fn take_effects(&mut self) -> Vec<(usize, Option<Cleanup>, Effect)> {
+        let mut effects = Vec::new();
+
+        for (index, hook) in self.hooks.iter_mut().enumerate() {
+            if let &mut Hook::Effect {
+                ref mut effect,
+                ref mut cleanup,
+                ..
+            } = hook
+            {
+                if let Some(effect) = effect.take() {
+                    effects.push((index, cleanup.take(), effect));
+                }
+            }
+        }
+
+        effects
+    }
+
+    #[inline]
+    pub fn set_cleanup(&mut self, index: usize, next_cleanup: Cleanup) {
+        if let Some(&mut Hook::Effect {
+            ref mut cleanup, ..
+        }) = self.hooks.get_mut(index)
+        {
+            *cleanup = Some(next_cleanup);
+        }
+    }
+
+    /// takes the cleanups of every effect that ran, queued effects are dropped
+    #[inline]
+    pub fn take_cleanups(&mut self) -> Vec<Cleanup> {
+        mem::replace(&mut self.hooks, Vec::new())
+            .into_iter()
+            .filter_map(|hook| match hook {
+                Hook::Effect { cleanup, .. } => cleanup,
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[inline]
+    fn next_index(&mut self) -> usize {
+        let index = self.index;
+        self.index += 1;
+        index
+    }
+
+    /// sets the slot at index, slots used in a different order than last render are replaced
+    #[inline]
+    fn set(&mut self, index: usize, hook: Hook) {
+        if index < self.hooks.len() {
+            self.hooks[index] = hook;
+        } else {
+            self.hooks.push(hook);
+        }
+    }
+}
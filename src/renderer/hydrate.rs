@@ -0,0 +1,121 @@
+use serde_json::{Map, Value};
+
+use super::super::{diff_props_object, view_id, Mismatch, Prop, Props, RawView, Transaction};
+
+/// compares an existing view against the next view, appending patches for every mismatch
+#[inline]
+pub fn hydrate_raw_view(
+    id: &str,
+    prev_view: &RawView,
+    next_view: &RawView,
+    transaction: &mut Transaction,
+    mismatches: &mut Vec<Mismatch>,
+) {
+    match (prev_view, next_view) {
+        (&RawView::Text(ref prev_text), &RawView::Text(ref next_text)) => {
+            if prev_text != next_text {
+                mismatches.push(Mismatch::Text(
+                    id.into(),
+                    next_text.clone(),
+                    prev_text.clone(),
+                ));
+                transaction.replace(id, prev_view.clone(), next_view.clone());
+            }
+        }
+        (
+            &RawView::Data {
+                kind: ref prev_kind,
+                key: ref prev_key,
+                props: ref prev_props,
+                children: ref prev_children,
+            },
+            &RawView::Data {
+                kind: ref next_kind,
+                key: ref next_key,
+                props: ref next_props,
+                children: ref next_children,
+            },
+        ) => {
+            if prev_kind != next_kind {
+                mismatches.push(Mismatch::Kind(
+                    id.into(),
+                    Some(next_kind.clone()),
+                    Some(prev_kind.clone()),
+                ));
+                transaction.replace(id, prev_view.clone(), next_view.clone());
+            } else if prev_key != next_key {
+                mismatches.push(Mismatch::Key(id.into(), next_key.clone(), prev_key.clone()));
+                transaction.replace(id, prev_view.clone(), next_view.clone());
+            } else {
+                hydrate_props(id, prev_props, next_props, transaction, mismatches);
+                hydrate_children(id, prev_children, next_children, transaction, mismatches);
+            }
+        }
+        (&RawView::Fragment(ref prev_children), &RawView::Fragment(ref next_children)) => {
+            hydrate_children(id, prev_children, next_children, transaction, mismatches);
+        }
+        _ => {
+            mismatches.push(Mismatch::Kind(
+                id.into(),
+                next_view.kind().map(Clone::clone),
+                prev_view.kind().map(Clone::clone),
+            ));
+            transaction.replace(id, prev_view.clone(), next_view.clone());
+        }
+    }
+}
+
+#[inline]
+fn hydrate_props(
+    id: &str,
+    prev_props: &Map<String, Value>,
+    next_props: &Map<String, Value>,
+    transaction: &mut Transaction,
+    mismatches: &mut Vec<Mismatch>,
+) {
+    if let Some(diff_props) =
+        diff_props_object(&json_to_props(prev_props), &json_to_props(next_props))
+    {
+        mismatches.push(Mismatch::Props(id.into()));
+        transaction.props(id, prev_props.clone(), diff_props.into());
+    }
+}
+
+#[inline]
+fn hydrate_children(
+    id: &str,
+    prev_children: &Vec<RawView>,
+    next_children: &Vec<RawView>,
+    transaction: &mut Transaction,
+    mismatches: &mut Vec<Mismatch>,
+) {
+    if prev_children.len() != next_children.len() {
+        mismatches.push(Mismatch::Children(
+            id.into(),
+            next_children.len(),
+            prev_children.len(),
+        ));
+    }
+
+    for (index, next_child) in next_children.iter().enumerate() {
+        let child_id = view_id(id, next_child.key(), index);
+
+        if let Some(prev_child) = prev_children.get(index) {
+            hydrate_raw_view(&child_id, prev_child, next_child, transaction, mismatches);
+        } else {
+            transaction.insert(id, &child_id, index, next_child.clone());
+        }
+    }
+
+    for (index, prev_child) in prev_children.iter().enumerate().skip(next_children.len()) {
+        let child_id = view_id(id, prev_child.key(), index);
+        transaction.remove(&child_id, prev_child.clone());
+    }
+}
+
+#[inline]
+fn json_to_props(map: &Map<String, Value>) -> Props {
+    map.iter()
+        .map(|(k, v)| (k.clone(), Prop::from(v)))
+        .collect()
+}
@@ -1,21 +1,35 @@
+use std::cell::RefCell;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::Future;
 use serde_json::Value;
 
-use super::super::Props;
-use super::Updater;
+use super::super::{Prop, Props, TypedContext};
+use super::{Hooks, LoadState, Ref, Request, SetState, SetStateAsync, Updater};
 
 pub struct Instance {
     pub state: Props,
     pub context: Props,
+    pub typed_context: TypedContext,
     pub updater: Updater,
+    hooks: RefCell<Hooks>,
 }
 
 impl Instance {
     #[inline]
-    pub(super) fn new(state: Props, context: Props, updater: Updater) -> Self {
+    pub(crate) fn new(
+        state: Props,
+        context: Props,
+        typed_context: TypedContext,
+        updater: Updater,
+    ) -> Self {
         Instance {
             state: state,
             context: context,
+            typed_context: typed_context,
             updater: updater,
+            hooks: RefCell::new(Hooks::new()),
         }
     }
 
@@ -29,6 +43,29 @@ impl Instance {
         self.updater.send(name, json, f)
     }
 
+    /// sends a message expecting one response, the returned future fails with
+    /// `RpcError::Host` if the host answers with an `error` key, `RpcError::Timeout` if it
+    /// does not answer before the renderer's request timeout, if one is set, and
+    /// `RpcError::Cancelled` if the component unmounts first
+    #[inline]
+    pub fn request<N, V>(&self, name: N, json: V) -> Request
+    where
+        N: Into<String>,
+        V: Into<Value>,
+    {
+        self.updater.request(name, json)
+    }
+
+    /// see `request`, with a timeout for this request
+    #[inline]
+    pub fn request_timeout<N, V>(&self, name: N, json: V, timeout: Option<Duration>) -> Request
+    where
+        N: Into<String>,
+        V: Into<Value>,
+    {
+        self.updater.request_timeout(name, json, timeout)
+    }
+
     #[inline]
     pub fn send_no_callback<N, V>(&self, name: N, json: V)
     where
@@ -38,6 +75,57 @@ impl Instance {
         self.updater.send_no_callback(name, json)
     }
 
+    /// calls f with the data of every `virtual_view.message` the host sends with name until
+    /// the component unmounts, returns the subscription id, usually called in `did_mount`,
+    /// f runs on the thread receiving host messages
+    #[inline]
+    pub fn subscribe<N, F>(&self, name: N, f: F) -> usize
+    where
+        N: Into<String>,
+        F: 'static + Send + Sync + Fn(&Value),
+    {
+        self.updater.subscribe(name, f)
+    }
+
+    #[inline]
+    pub fn unsubscribe(&self, name: &str, subscription_id: usize) {
+        self.updater.unsubscribe(name, subscription_id)
+    }
+
+    /// calls f once after delay unless the component unmounts first, returns the timer id
+    #[inline]
+    pub fn set_timeout<F>(&self, delay: Duration, f: F) -> usize
+    where
+        F: 'static + Send + Sync + Fn(),
+    {
+        self.updater.set_timeout(delay, f)
+    }
+
+    /// calls f every interval until the timer is cleared or the component unmounts, returns
+    /// the timer id
+    #[inline]
+    pub fn set_interval<F>(&self, interval: Duration, f: F) -> usize
+    where
+        F: 'static + Send + Sync + Fn(),
+    {
+        self.updater.set_interval(interval, f)
+    }
+
+    /// calls f before the host draws its next frame unless the component unmounts first,
+    /// returns the timer id
+    #[inline]
+    pub fn request_frame<F>(&self, f: F) -> usize
+    where
+        F: 'static + Send + Sync + Fn(),
+    {
+        self.updater.request_frame(f)
+    }
+
+    #[inline]
+    pub fn clear_timer(&self, timer_id: usize) {
+        self.updater.clear_timer(timer_id)
+    }
+
     #[inline(always)]
     pub fn state(&self) -> &Props {
         &self.state
@@ -47,6 +135,18 @@ impl Instance {
         &self.context
     }
     #[inline(always)]
+    pub fn typed_context(&self) -> &TypedContext {
+        &self.typed_context
+    }
+    /// the typed context value of type T
+    #[inline]
+    pub fn context_get<T>(&self) -> Option<Arc<T>>
+    where
+        T: 'static + Send + Sync,
+    {
+        self.typed_context.get::<T>()
+    }
+    #[inline(always)]
     pub fn updater(&self) -> &Updater {
         &self.updater
     }
@@ -59,8 +159,135 @@ impl Instance {
         self.updater.set_state(f)
     }
 
+    /// see `Updater::set_state_async`
+    #[inline]
+    pub fn set_state_async<T, F>(&self, future: T) -> SetStateAsync<T>
+    where
+        T: Future<Item = F>,
+        F: 'static + Send + Fn(&Props) -> Props,
+    {
+        self.updater.set_state_async(future)
+    }
+
+    /// see `Updater::load`
+    #[inline]
+    pub fn load<T, F>(&self, future: T) -> LoadState<T>
+    where
+        T: Future<Item = F>,
+        T::Error: Into<Prop>,
+        F: 'static + Send + Fn(&Props) -> Props,
+    {
+        self.updater.load(future)
+    }
+    /// true while a future passed to `load` has not resolved
+    #[inline]
+    pub fn is_loading(&self) -> bool {
+        self.state.get("loading").is_true()
+    }
+    /// the error the last future passed to `load` failed with
+    #[inline]
+    pub fn load_error(&self) -> Option<&Prop> {
+        match self.state.get("error") {
+            &Prop::Null => None,
+            error => Some(error),
+        }
+    }
+
     #[inline]
     pub fn force_update(&self) {
         self.updater.force_update()
     }
+
+    /// the state at key, or init if the key was never set, with a setter for the key
+    ///
+    /// hooks must be used in the same order every render
+    #[inline]
+    pub fn use_state<K, V>(&self, key: K, init: V) -> (Prop, SetState)
+    where
+        K: Into<String>,
+        V: Into<Prop>,
+    {
+        let key = key.into();
+        let init = self.hooks.borrow_mut().state(init.into());
+
+        let value = if self.state.has(&key) {
+            self.state.get(&key).clone()
+        } else {
+            init.clone()
+        };
+
+        (value, SetState::new(key, init, self.updater.clone()))
+    }
+
+    /// runs effect after the render is sent to the host on the first render, every render
+    /// if deps is null or when deps change, the returned cleanup runs before the effect
+    /// runs again and when the component unmounts
+    #[inline]
+    pub fn use_effect<D, F, C>(&self, deps: D, effect: F)
+    where
+        D: Into<Prop>,
+        F: 'static + FnOnce() -> C,
+        C: 'static + FnOnce(),
+    {
+        self.hooks.borrow_mut().effect(
+            deps.into(),
+            Box::new(move || -> Box<dyn FnOnce()> { Box::new(effect()) }),
+        );
+    }
+
+    /// the result of f, only called again if deps is null or when deps change
+    #[inline]
+    pub fn use_memo<D, T, F>(&self, deps: D, f: F) -> T
+    where
+        D: Into<Prop>,
+        T: 'static + Clone,
+        F: FnOnce() -> T,
+    {
+        let deps = deps.into();
+        let (index, value) = self.hooks.borrow_mut().memo(&deps);
+
+        match value {
+            Some(value) => value,
+            None => {
+                let value = f();
+                self.hooks.borrow_mut().set_memo(index, deps, value.clone());
+                value
+            }
+        }
+    }
+
+    /// a ref that is the same every render, pass it as the `ref` prop of a view
+    #[inline]
+    pub fn use_ref(&self) -> Ref {
+        self.use_memo(Vec::<Prop>::new(), || Ref::new(&self.updater))
+    }
+
+    #[inline]
+    pub(crate) fn start_render(&self) {
+        self.hooks.borrow_mut().start_render();
+    }
+
+    /// runs the effects queued by the last render
+    #[inline]
+    pub(crate) fn run_effects(&self) {
+        let effects = self.hooks.borrow_mut().take_effects();
+
+        for (index, cleanup, effect) in effects {
+            if let Some(cleanup) = cleanup {
+                cleanup();
+            }
+            let cleanup = effect();
+            self.hooks.borrow_mut().set_cleanup(index, cleanup);
+        }
+    }
+
+    /// runs the cleanups of the effects that ran
+    #[inline]
+    pub(crate) fn run_cleanups(&self) {
+        let cleanups = self.hooks.borrow_mut().take_cleanups();
+
+        for cleanup in cleanups {
+            cleanup();
+        }
+    }
 }
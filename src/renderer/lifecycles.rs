@@ -0,0 +1,98 @@
+use std::cell::RefCell;
+use std::mem;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use super::super::{is_ancestor_id_of, Function, Prop, Props};
+use super::Node;
+
+pub enum Lifecycle {
+    Mount(Node),
+    Update(Node, Props, Props),
+    Unmount(Node),
+    /// calls a `ref` prop with the id of its view, or null after the view unmounts
+    Ref(String, Arc<Function>, bool),
+}
+
+impl Lifecycle {
+    #[inline]
+    pub fn run(&self) {
+        match self {
+            &Lifecycle::Mount(ref node) => node.as_ref().did_mount(),
+            &Lifecycle::Update(ref node, ref prev_props, ref prev_state) => {
+                node.as_ref().did_update(prev_props, prev_state)
+            }
+            &Lifecycle::Unmount(ref node) => node.as_ref().did_unmount(),
+            &Lifecycle::Ref(ref id, ref func, mounted) => {
+                let id = if mounted {
+                    Prop::from(id.as_str())
+                } else {
+                    Prop::Null
+                };
+                let mut props = Props::new();
+                props.set("id", id);
+                (&**func)(&mut props);
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Lifecycles(Rc<RefCell<Vec<Lifecycle>>>);
+
+impl Lifecycles {
+    #[inline]
+    pub fn new() -> Self {
+        Lifecycles(Rc::new(RefCell::new(Vec::new())))
+    }
+
+    #[inline]
+    pub fn push(&self, lifecycle: Lifecycle) {
+        self.0.borrow_mut().push(lifecycle);
+    }
+
+    /// drops the lifecycles of every node rendered below the node at id and depth, returns
+    /// the nodes that were waiting to mount
+    #[inline]
+    pub fn remove_subtree(&self, id: &str, depth: usize) -> Vec<Node> {
+        let mut mounting = Vec::new();
+
+        self.0.borrow_mut().retain(|lifecycle| match lifecycle {
+            &Lifecycle::Mount(ref node) => {
+                let in_subtree = node.is_in_subtree(id, depth);
+                if in_subtree {
+                    mounting.push(node.clone());
+                }
+                !in_subtree
+            }
+            &Lifecycle::Update(ref node, _, _) => !node.is_in_subtree(id, depth),
+            &Lifecycle::Unmount(ref node) => !node.is_in_subtree(id, depth),
+            &Lifecycle::Ref(ref ref_id, _, _) => !is_ancestor_id_of(id, ref_id),
+        });
+
+        mounting
+    }
+
+    /// drops the lifecycles of the node, returns true if it was waiting to mount
+    #[inline]
+    pub fn remove_node(&self, node: &Node) -> bool {
+        let mut mounting = false;
+
+        self.0.borrow_mut().retain(|lifecycle| match lifecycle {
+            &Lifecycle::Mount(ref other) if other.ptr_eq(node) => {
+                mounting = true;
+                false
+            }
+            &Lifecycle::Update(ref other, _, _) => !other.ptr_eq(node),
+            &Lifecycle::Unmount(ref other) => !other.ptr_eq(node),
+            _ => true,
+        });
+
+        mounting
+    }
+
+    #[inline]
+    pub fn take(&self) -> Vec<Lifecycle> {
+        mem::replace(&mut *self.0.borrow_mut(), Vec::new())
+    }
+}
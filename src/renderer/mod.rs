@@ -1,13 +1,33 @@
+mod hooks;
+mod hydrate;
 mod instance;
+mod lifecycles;
 mod node;
 mod nodes;
 mod queue;
 mod renderer;
+mod requests;
+mod rpc_error;
+mod set_state;
+mod set_state_async;
+mod subscriptions;
+mod timers;
 mod updater;
+mod view_ref;
 
+pub use self::hooks::Hooks;
+pub use self::hydrate::hydrate_raw_view;
 pub use self::instance::Instance;
-pub use self::node::{Node, NodeInner, NodeKind};
+pub use self::lifecycles::{Lifecycle, Lifecycles};
+pub use self::node::{catch_render_error, Node, NodeInner, NodeKind};
 pub use self::nodes::Nodes;
 pub use self::queue::{Message, Queue};
 pub use self::renderer::Renderer;
+pub use self::requests::{Request, Requests};
+pub use self::rpc_error::RpcError;
+pub use self::set_state::SetState;
+pub use self::set_state_async::{LoadState, SetStateAsync};
+pub use self::subscriptions::Subscriptions;
+pub use self::timers::{Callback as TimerCallback, Timers};
 pub use self::updater::Updater;
+pub use self::view_ref::Ref;
@@ -1,13 +1,15 @@
 use std::cell::{Ref, RefCell, RefMut};
-use std::ptr;
+use std::panic::{self, AssertUnwindSafe};
 use std::rc::Rc;
 use std::sync::Arc;
+use std::{mem, ptr};
 
+use super::super::view::flatten_children;
 use super::super::{
-    diff_children, diff_props_object, parent_id, view_id, Children, Component, Instance, Props,
-    Transaction, Updater, View,
+    diff_children, diff_props_object, is_ancestor_id_of, parent_id, view_id, Children, Component,
+    Instance, Props, RenderError, Transaction, TypedContext, Updater, View,
 };
-use super::Renderer;
+use super::{Lifecycle, Renderer};
 
 pub enum NodeKind {
     View,
@@ -27,6 +29,7 @@ pub struct NodeInner {
     pub view: View,
     pub kind: NodeKind,
     pub parent_context: Props,
+    pub parent_typed_context: TypedContext,
 }
 
 impl NodeInner {
@@ -38,26 +41,52 @@ impl NodeInner {
         renderer: &Renderer,
         view: View,
         parent_context: &Props,
+        parent_typed_context: &TypedContext,
     ) -> Self {
         let kind = if let Some(component) = view.component().map(Clone::clone) {
             let mut context = component.context(view.props().unwrap());
             context = component.inherit_context(context, parent_context);
 
+            let mut typed_context = component.typed_context(view.props().unwrap());
+            typed_context = component.inherit_typed_context(typed_context, parent_typed_context);
+
             let state = component.initial_state(view.props().unwrap());
             let updater = Updater::new(id.clone(), depth, renderer.clone());
-            let instance = Instance::new(state, context, updater);
+            let instance = Instance::new(state, context, typed_context, updater);
 
             let rendered_view = Self::render_component_view(&instance, &view, &component);
 
-            NodeKind::Component {
-                node: Node::new(
+            let node = match catch_render_error(|| {
+                Node::new(
                     index,
                     depth + 1,
                     id.clone(),
                     renderer,
                     rendered_view,
                     &instance.context,
-                ),
+                    &instance.typed_context,
+                )
+            }) {
+                Ok(node) => node,
+                Err(error) => {
+                    let fallback = Self::catch_error(&instance, &view, &component, error);
+
+                    renderer.clear_subtree(&id, depth, None);
+
+                    Node::new(
+                        index,
+                        depth + 1,
+                        id.clone(),
+                        renderer,
+                        fallback,
+                        &instance.context,
+                        &instance.typed_context,
+                    )
+                }
+            };
+
+            NodeKind::Component {
+                node: node,
                 instance: instance,
                 next_state: None,
                 component: component,
@@ -74,11 +103,13 @@ impl NodeInner {
             view: view,
             kind: kind,
             parent_context: parent_context.clone(),
+            parent_typed_context: parent_typed_context.clone(),
         }
     }
 
+    /// queues a state change, applied on top of any state change not yet rendered
     #[inline]
-    fn set_next_state<F>(&mut self, f: F)
+    pub fn set_next_state<F>(&mut self, f: F)
     where
         F: Fn(&Props) -> Props,
     {
@@ -88,19 +119,24 @@ impl NodeInner {
                 ref mut next_state,
                 ..
             } => {
-                *next_state = Some(f(&instance.state));
+                let state = f(next_state.as_ref().unwrap_or(&instance.state));
+                *next_state = Some(state);
             }
             _ => (),
         }
     }
 
     #[inline]
-    pub fn update_state<F>(&mut self, f: F, transaction: &mut Transaction)
-    where
-        F: Fn(&Props) -> Props,
-    {
-        self.set_next_state(f);
+    pub fn has_next_state(&self) -> bool {
+        match &self.kind {
+            &NodeKind::Component { ref next_state, .. } => next_state.is_some(),
+            _ => false,
+        }
+    }
 
+    /// renders the queued state changes
+    #[inline]
+    pub fn update_next_state(&mut self, transaction: &mut Transaction) {
         let prev_view = self.view.clone();
         let next_view = self.view.clone();
 
@@ -111,14 +147,16 @@ impl NodeInner {
     fn next_state(&mut self) -> Props {
         match &mut self.kind {
             &mut NodeKind::Component {
-                ref mut next_state, ..
-            } => next_state.take().unwrap_or_else(Props::new),
+                ref instance,
+                ref mut next_state,
+                ..
+            } => next_state.take().unwrap_or_else(|| instance.state.clone()),
             _ => Props::new(),
         }
     }
 
     #[inline]
-    fn render_component_view(
+    pub(crate) fn render_component_view(
         instance: &Instance,
         view: &View,
         component: &Arc<dyn Component>,
@@ -129,7 +167,18 @@ impl NodeInner {
         let props = view.props().unwrap_or(&empty_props);
         let children = view.children().unwrap_or(&empty_children);
 
-        let mut rendered_view = component.render(instance, props, children);
+        instance.start_render();
+
+        let mut rendered_view = match panic::catch_unwind(AssertUnwindSafe(|| {
+            component.render(instance, props, children)
+        })) {
+            Ok(rendered_view) => rendered_view,
+            Err(payload) => panic::resume_unwind(Box::new(RenderError::from_panic(
+                instance.updater.id().clone(),
+                component.name(),
+                &payload,
+            ))),
+        };
 
         if !rendered_view.has_key() {
             rendered_view.set_key_option(view.clone_key());
@@ -138,47 +187,197 @@ impl NodeInner {
         rendered_view
     }
 
+    /// asks the component for a fallback view, passing the error on if it has none
+    #[inline]
+    pub fn catch_error(
+        instance: &Instance,
+        view: &View,
+        component: &Arc<dyn Component>,
+        error: RenderError,
+    ) -> View {
+        match component.catch_error(instance, &error) {
+            Some(mut fallback) => {
+                if !fallback.has_key() {
+                    fallback.set_key_option(view.clone_key());
+                }
+                fallback
+            }
+            None => panic::resume_unwind(Box::new(error)),
+        }
+    }
+
+    /// replaces the failed child node with the component's fallback view, prev_view is the
+    /// view the host currently has if any
+    #[inline]
+    fn recover(
+        &mut self,
+        error: RenderError,
+        prev_view: Option<View>,
+        transaction: &mut Transaction,
+    ) -> View {
+        let (fallback, context, typed_context) = match &self.kind {
+            &NodeKind::Component {
+                ref instance,
+                ref component,
+                ..
+            } => (
+                Self::catch_error(instance, &self.view, component, error),
+                instance.context.clone(),
+                instance.typed_context.clone(),
+            ),
+            &NodeKind::View => panic::resume_unwind(Box::new(error)),
+        };
+
+        transaction.clear_subtree(&self.id);
+
+        let host_transaction = if prev_view.is_some() {
+            Some(&mut *transaction)
+        } else {
+            None
+        };
+        self.renderer
+            .clear_subtree(&self.id, self.depth, host_transaction);
+
+        let node = Node::new(
+            self.index,
+            self.depth + 1,
+            self.id.clone(),
+            &self.renderer,
+            fallback,
+            &context,
+            &typed_context,
+        );
+        let view = node.mount(transaction);
+
+        if let Some(prev_view) = prev_view {
+            transaction.replace(&self.id, (&prev_view).into(), view.clone().into());
+        }
+
+        match &mut self.kind {
+            &mut NodeKind::Component {
+                node: ref mut child,
+                ..
+            } => *child = node,
+            &mut NodeKind::View => (),
+        }
+
+        view
+    }
+
     #[inline]
     fn rendered_view(&self) -> View {
         match &self.kind {
             &NodeKind::Component { ref node, .. } => node.rendered_view(),
-            &NodeKind::View => self.view.clone(),
+            &NodeKind::View => {
+                if self.view.portal_target().is_some() {
+                    // portals leave an empty text in their parent, their children live in the target
+                    View::Text(String::new())
+                } else {
+                    self.view.clone()
+                }
+            }
         }
     }
 
+    /// the id and view of the fragment a portal rendered into its target
     #[inline]
-    pub fn mount(&mut self, transaction: &mut Transaction) -> View {
+    fn portal_fragment(&self) -> Option<(String, View)> {
+        match &self.kind {
+            &NodeKind::View if self.view.portal_target().is_some() => Some((
+                view_children_id(&self.id, &self.view),
+                View::Fragment(self.view.children().map(Clone::clone).unwrap_or_default()),
+            )),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn did_mount(&self) {
+        if let &NodeKind::Component {
+            ref instance,
+            ref component,
+            ..
+        } = &self.kind
+        {
+            component.did_mount(instance);
+            instance.run_effects();
+        }
+    }
+
+    #[inline]
+    pub fn did_update(&self, prev_props: &Props, prev_state: &Props) {
+        if let &NodeKind::Component {
+            ref instance,
+            ref component,
+            ..
+        } = &self.kind
+        {
+            component.did_update(instance, prev_props, prev_state);
+            instance.run_effects();
+        }
+    }
+
+    /// runs the unmount hooks of a node removed without unmounting it, the requests,
+    /// subscriptions and timers below it are cleared with the subtree
+    #[inline]
+    fn unmount_removed(&self, this: &Node) {
         match &self.kind {
             &NodeKind::Component {
                 ref instance,
-                ref node,
                 ref component,
                 ..
             } => {
-                let mut view = node.mount(transaction);
+                component.will_unmount(instance);
+                instance.run_cleanups();
+                instance.updater.set_unmounted();
+                self.renderer
+                    .lifecycles()
+                    .push(Lifecycle::Unmount(this.clone()));
+            }
+            &NodeKind::View => {
+                if let Some(props) = self.view.props() {
+                    self.renderer.unmount_props_ref(&self.id, props);
+                }
+            }
+        }
+    }
 
-                component.will_mount(instance);
+    #[inline]
+    pub fn did_unmount(&self) {
+        if let &NodeKind::Component {
+            ref instance,
+            ref component,
+            ..
+        } = &self.kind
+        {
+            component.did_unmount(instance);
+        }
+    }
 
-                match &mut view {
-                    &mut View::Data {
-                        ref mut children, ..
-                    } => {
-                        for (index, child) in children.iter_mut().enumerate() {
-                            if child.is_data() {
-                                let child_id = view_id(&self.id, child.key(), index);
-                                let node = Node::new(
-                                    index,
-                                    0,
-                                    child_id,
-                                    &self.renderer,
-                                    child.clone(),
-                                    &instance.context,
-                                );
-                                *child = node.mount(transaction);
-                            }
-                        }
-                    }
-                    _ => (),
+    #[inline]
+    pub fn mount(&mut self, transaction: &mut Transaction) -> View {
+        match &self.kind {
+            &NodeKind::Component { ref node, .. } => {
+                let view = match catch_render_error(|| node.mount(transaction)) {
+                    Ok(view) => view,
+                    Err(error) => self.recover(error, None, transaction),
+                };
+
+                if let &NodeKind::Component {
+                    ref instance,
+                    ref component,
+                    ..
+                } = &self.kind
+                {
+                    component.will_mount(instance);
+                }
+
+                if let Some(this) = self
+                    .renderer
+                    .nodes()
+                    .get_at_depth(self.id.clone(), self.depth)
+                {
+                    self.renderer.lifecycles().push(Lifecycle::Mount(this));
                 }
 
                 view
@@ -187,31 +386,47 @@ impl NodeInner {
                 if let Some(props) = self.view.props() {
                     self.renderer
                         .mount_props_events(&self.id, props, transaction);
+                    self.renderer.mount_props_ref(&self.id, props);
                 }
 
-                match &mut self.view {
-                    &mut View::Data {
-                        ref mut children, ..
-                    } => {
+                let target = self.view.portal_target().map(Clone::clone);
+                let children_id = view_children_id(&self.id, &self.view);
+
+                match self.view.children_mut() {
+                    Some(children) => {
+                        flatten_children(children);
+
                         for (index, child) in children.iter_mut().enumerate() {
+                            let child_id = view_id(&children_id, child.key(), index);
+
                             if child.is_data() {
-                                let child_id = view_id(&self.id, child.key(), index);
                                 let node = Node::new(
                                     index,
                                     0,
-                                    child_id,
+                                    child_id.clone(),
                                     &self.renderer,
                                     child.clone(),
                                     &self.parent_context,
+                                    &self.parent_typed_context,
                                 );
                                 *child = node.mount(transaction);
                             }
                         }
                     }
-                    _ => (),
+                    None => (),
                 }
 
-                self.view.clone()
+                if let (Some(target), Some((id, view))) = (target, self.portal_fragment()) {
+                    let index = self
+                        .renderer
+                        .event_manager()
+                        .write()
+                        .add_portal(&target, &self.id);
+
+                    transaction.insert(&target, &id, index, view.into());
+                }
+
+                self.rendered_view()
             }
         }
     }
@@ -228,6 +443,21 @@ impl NodeInner {
                 let mut view = node.unmount(transaction);
 
                 component.will_unmount(instance);
+                instance.run_cleanups();
+                self.renderer.requests().cancel(&self.id, self.depth);
+                self.renderer
+                    .subscriptions()
+                    .remove_component(&self.id, self.depth);
+                self.renderer.clear_timers(&self.id, self.depth);
+                instance.updater.set_unmounted();
+
+                if let Some(this) = self
+                    .renderer
+                    .nodes()
+                    .get_at_depth(self.id.clone(), self.depth)
+                {
+                    self.renderer.lifecycles().push(Lifecycle::Unmount(this));
+                }
 
                 match &mut view {
                     &mut View::Data {
@@ -250,24 +480,33 @@ impl NodeInner {
                 if let Some(props) = self.view.props() {
                     self.renderer
                         .unmount_props_events(&self.id, props, transaction);
+                    self.renderer.unmount_props_ref(&self.id, props);
                 }
 
-                match &mut self.view {
-                    &mut View::Data {
-                        ref mut children, ..
-                    } => {
-                        for (index, child) in children.iter_mut().enumerate() {
-                            let child_id = view_id(&self.id, child.key(), index);
+                let children_id = view_children_id(&self.id, &self.view);
 
-                            if let Some(node) = self.renderer.nodes().get(child_id) {
-                                *child = node.unmount(transaction);
-                            }
+                if let Some(children) = self.view.children_mut() {
+                    for (index, child) in children.iter_mut().enumerate() {
+                        let child_id = view_id(&children_id, child.key(), index);
+
+                        if let Some(node) = self.renderer.nodes().get(child_id) {
+                            *child = node.unmount(transaction);
                         }
                     }
-                    _ => (),
                 }
 
-                self.view.clone()
+                if let Some((id, view)) = self.portal_fragment() {
+                    transaction.remove(&id, view.into());
+
+                    if let Some(target) = self.view.portal_target() {
+                        self.renderer
+                            .event_manager()
+                            .write()
+                            .remove_portal(target, &self.id);
+                    }
+                }
+
+                self.rendered_view()
             }
         };
 
@@ -284,6 +523,69 @@ impl NodeInner {
         self.update(prev_view, next_view, transaction)
     }
 
+    /// receives next_view with the context of the parent as it is now
+    #[inline]
+    pub fn receive_with_context(
+        &mut self,
+        next_view: View,
+        parent_context: &Props,
+        parent_typed_context: &TypedContext,
+        transaction: &mut Transaction,
+    ) -> View {
+        if &self.parent_context != parent_context {
+            self.parent_context = parent_context.clone();
+        }
+        if &self.parent_typed_context != parent_typed_context {
+            self.parent_typed_context = parent_typed_context.clone();
+        }
+        self.receive(next_view, transaction)
+    }
+
+    /// passes a changed parent context down without new views, components whose context
+    /// changes update, the ones that should not update pass their context on to their
+    /// children
+    #[inline]
+    pub fn update_context(
+        &mut self,
+        parent_context: &Props,
+        parent_typed_context: &TypedContext,
+        transaction: &mut Transaction,
+    ) -> View {
+        if &self.parent_context == parent_context
+            && &self.parent_typed_context == parent_typed_context
+        {
+            return self.rendered_view();
+        }
+        self.parent_context = parent_context.clone();
+        self.parent_typed_context = parent_typed_context.clone();
+
+        match &mut self.kind {
+            &mut NodeKind::Component { .. } => {
+                let view = self.view.clone();
+                return self.internal_update(view.clone(), view, transaction);
+            }
+            &mut NodeKind::View => (),
+        }
+
+        let children_id = view_children_id(&self.id, &self.view);
+
+        if let Some(children) = self.view.children_mut() {
+            for (index, child) in children.iter_mut().enumerate() {
+                let child_id = view_id(&children_id, child.key(), index);
+
+                if let Some(node) = self.renderer.nodes().get(child_id) {
+                    *child = node.update_context(
+                        &self.parent_context,
+                        &self.parent_typed_context,
+                        transaction,
+                    );
+                }
+            }
+        }
+
+        self.rendered_view()
+    }
+
     #[inline]
     pub fn update(
         &mut self,
@@ -303,6 +605,7 @@ impl NodeInner {
                 &self.renderer,
                 next_view,
                 &self.parent_context,
+                &self.parent_typed_context,
             );
             let view = node.mount(transaction);
             transaction.replace(&self.id, self.rendered_view().into(), view.clone().into());
@@ -323,12 +626,10 @@ impl NodeInner {
                     key: ref next_key,
                     ..
                 } => prev_kind == next_kind && prev_key == next_key,
-                &View::Text(_) => false,
-            },
-            &View::Text(_) => match next_view {
-                &View::Data { .. } => false,
-                &View::Text(_) => true,
+                _ => false,
             },
+            &View::Text(_) => next_view.is_text(),
+            &View::Fragment(_) => next_view.is_fragment(),
         }
     }
 
@@ -348,8 +649,25 @@ impl NodeInner {
                 ref node,
                 ..
             } => {
+                let empty_props = Props::new();
+
+                let next_context = component.inherit_context(
+                    component.context(next_view.props().unwrap_or(&empty_props)),
+                    &self.parent_context,
+                );
+                let next_typed_context = component.inherit_typed_context(
+                    component.typed_context(next_view.props().unwrap_or(&empty_props)),
+                    &self.parent_typed_context,
+                );
+                let context_changed = next_context != instance.context
+                    || next_typed_context != instance.typed_context;
+
+                if context_changed {
+                    instance.context = next_context;
+                    instance.typed_context = next_typed_context;
+                }
+
                 let should_update = {
-                    let empty_props = Props::new();
                     let empty_children = Children::new();
 
                     let next_props = next_view.props().unwrap_or(&empty_props);
@@ -375,117 +693,197 @@ impl NodeInner {
                 };
 
                 self.view = next_view;
-                instance.state = next_state;
+                let prev_state = mem::replace(&mut instance.state, next_state);
 
                 if should_update {
-                    node.receive(
-                        Self::render_component_view(instance, &self.view, component),
-                        transaction,
-                    )
+                    let rendered_view =
+                        Self::render_component_view(instance, &self.view, component);
+
+                    let context = &instance.context;
+                    let typed_context = &instance.typed_context;
+                    let view = match catch_render_error(|| {
+                        node.receive_with_context(
+                            rendered_view,
+                            context,
+                            typed_context,
+                            transaction,
+                        )
+                    }) {
+                        Ok(view) => view,
+                        Err(error) => {
+                            let prev_view = node.rendered_view();
+                            self.recover(error, Some(prev_view), transaction)
+                        }
+                    };
+
+                    if let Some(this) = self
+                        .renderer
+                        .nodes()
+                        .get_at_depth(self.id.clone(), self.depth)
+                    {
+                        let prev_props = prev_view.props().map(Clone::clone);
+
+                        self.renderer.lifecycles().push(Lifecycle::Update(
+                            this,
+                            prev_props.unwrap_or_else(Props::new),
+                            prev_state,
+                        ));
+                    }
+
+                    view
+                } else if context_changed {
+                    // the component keeps its view, but the nodes below it get the new context
+                    let context = &instance.context;
+                    let typed_context = &instance.typed_context;
+
+                    match catch_render_error(|| {
+                        node.update_context(context, typed_context, transaction)
+                    }) {
+                        Ok(view) => view,
+                        Err(error) => {
+                            let prev_view = node.rendered_view();
+                            self.recover(error, Some(prev_view), transaction)
+                        }
+                    }
                 } else {
                     node.rendered_view()
                 }
             }
             &mut NodeKind::View => {
+                let mut next_view = next_view;
+
+                if let Some(children) = next_view.children_mut() {
+                    flatten_children(children);
+                }
+
                 let mut view = next_view.clone_no_children();
 
-                match &next_view {
-                    &View::Data {
-                        props: ref next_props,
-                        children: ref next_children,
-                        ..
-                    } => {
-                        let view_children = view.children_mut().unwrap();
-
-                        let empty_children = Children::new();
-                        let prev_children = prev_view.children().unwrap_or(&empty_children);
-                        let children_diff = diff_children(prev_children, next_children);
-
-                        let empty_props = Props::new();
-                        let prev_props = prev_view.props().unwrap_or(&empty_props);
-
-                        view_children.reserve(children_diff.next_len());
-
-                        for (index, next_view_option) in children_diff.children.iter().enumerate() {
-                            let prev_view_option = prev_children.get(index);
-
-                            if let &Some(next_view) = next_view_option {
-                                let next_view_id = view_id(&self.id, next_view.key(), index);
-
-                                if let Some(prev_view) = prev_view_option {
-                                    let prev_view_id = view_id(&self.id, prev_view.key(), index);
-
-                                    if let Some(node) = self.renderer.nodes().get(prev_view_id) {
-                                        let view = node.receive(next_view.clone(), transaction);
-                                        view_children.push(view);
-                                    } else {
-                                        if &prev_view != &next_view {
-                                            transaction.replace(
-                                                &next_view_id,
-                                                prev_view.into(),
-                                                next_view.into(),
-                                            );
-                                        }
-                                        view_children.push(next_view.clone());
-                                    }
-                                } else {
-                                    let node = Node::new(
-                                        index,
-                                        0,
-                                        next_view_id.clone(),
-                                        &self.renderer,
+                let children_id = &view_children_id(&self.id, &next_view);
+
+                if let Some(next_children) = next_view.children() {
+                    let empty_props = Props::new();
+                    let next_props = next_view.props().unwrap_or(&empty_props);
+                    let view_children = view.children_mut().unwrap();
+
+                    let empty_children = Children::new();
+                    let prev_children = prev_view.children().unwrap_or(&empty_children);
+                    let children_diff = diff_children(prev_children, next_children);
+
+                    let prev_props = prev_view.props().unwrap_or(&empty_props);
+
+                    view_children.reserve(children_diff.next_len());
+
+                    for (index, next_view_option) in children_diff.children.iter().enumerate() {
+                        let prev_view_option = prev_children.get(index);
+
+                        if let &Some(next_view) = next_view_option {
+                            let next_view_id = view_id(children_id, next_view.key(), index);
+
+                            if let Some(prev_view) = prev_view_option {
+                                let prev_view_id = view_id(children_id, prev_view.key(), index);
+
+                                if let Some(node) = self.renderer.nodes().get(prev_view_id) {
+                                    let view = node.receive_with_context(
                                         next_view.clone(),
                                         &self.parent_context,
-                                    );
-                                    let view = node.mount(transaction);
-                                    transaction.insert(
-                                        &self.id,
-                                        &next_view_id,
-                                        index,
-                                        view.clone().into(),
+                                        &self.parent_typed_context,
+                                        transaction,
                                     );
                                     view_children.push(view);
+                                } else {
+                                    if &prev_view != &next_view {
+                                        transaction.replace(
+                                            &next_view_id,
+                                            prev_view.into(),
+                                            next_view.into(),
+                                        );
+                                    }
+                                    view_children.push(next_view.clone());
                                 }
-                            } else if let Some(prev_view) = prev_view_option {
-                                let prev_view_id = view_id(&self.id, prev_view.key(), index);
+                            } else {
+                                let node = Node::new(
+                                    index,
+                                    0,
+                                    next_view_id.clone(),
+                                    &self.renderer,
+                                    next_view.clone(),
+                                    &self.parent_context,
+                                    &self.parent_typed_context,
+                                );
+                                let view = node.mount(transaction);
+                                transaction.insert(
+                                    children_id,
+                                    &next_view_id,
+                                    index,
+                                    view.clone().into(),
+                                );
+                                view_children.push(view);
+                            }
+                        } else if let Some(prev_view) = prev_view_option {
+                            let prev_view_id = view_id(children_id, prev_view.key(), index);
 
-                                if let Some(node) = self.renderer.nodes().get(prev_view_id.clone())
-                                {
-                                    let view = node.unmount(transaction);
-                                    transaction.remove(&prev_view_id, view.into());
-                                }
+                            if let Some(node) = self.renderer.nodes().get(prev_view_id.clone()) {
+                                let view = node.unmount(transaction);
+                                transaction.remove(&prev_view_id, view.into());
                             }
                         }
+                    }
 
-                        reorder_children(view_children, &children_diff.indices);
-
-                        if let Some(diff_props) = diff_props_object(prev_props, next_props) {
-                            transaction.props(&self.id, prev_props.into(), diff_props.into());
-                        }
+                    reorder_children(view_children, &children_diff.indices);
 
-                        let order = children_diff.into_order();
-                        if !order.is_empty() {
-                            transaction.order(&self.id, order);
-                        }
+                    if let Some(diff_props) = diff_props_object(prev_props, next_props) {
+                        transaction.props(&self.id, prev_props.into(), diff_props.into());
+                    }
 
-                        self.renderer.update_props_events(
-                            &self.id,
-                            prev_props,
-                            next_props,
-                            transaction,
-                        );
+                    let order = children_diff.into_order();
+                    if !order.is_empty() {
+                        transaction.order(children_id, order);
                     }
-                    _ => (),
+
+                    self.renderer.update_props_events(
+                        &self.id,
+                        prev_props,
+                        next_props,
+                        transaction,
+                    );
+                    self.renderer
+                        .update_props_ref(&self.id, prev_props, next_props);
                 }
 
-                self.view = view.clone();
+                self.view = view;
 
-                view
+                self.rendered_view()
             }
         }
     }
 }
 
+/// the id the children of the view are rendered below, portals render their children into
+/// a fragment below their own id, so several portals can render into the same target
+#[inline]
+fn view_children_id(id: &str, view: &View) -> String {
+    if view.portal_target().is_some() {
+        view_id(id, None, 0)
+    } else {
+        id.to_owned()
+    }
+}
+
+/// runs f, returning the error if a component rendered below panics
+#[inline]
+pub fn catch_render_error<F, T>(f: F) -> Result<T, RenderError>
+where
+    F: FnOnce() -> T,
+{
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(value) => Ok(value),
+        Err(payload) => match payload.downcast::<RenderError>() {
+            Ok(error) => Err(*error),
+            Err(payload) => panic::resume_unwind(payload),
+        },
+    }
+}
+
 #[inline]
 fn reorder_children(children: &mut Vec<View>, indices: &Vec<usize>) {
     let len = children.len();
@@ -516,6 +914,7 @@ impl Node {
         renderer: &Renderer,
         view: View,
         parent_context: &Props,
+        parent_typed_context: &TypedContext,
     ) -> Self {
         let node = Node(Rc::new(RefCell::new(NodeInner::new(
             index,
@@ -524,6 +923,7 @@ impl Node {
             renderer,
             view,
             parent_context,
+            parent_typed_context,
         ))));
 
         renderer.nodes().insert_at_depth(id, depth, node.clone());
@@ -545,6 +945,56 @@ impl Node {
         self.as_ref().rendered_view()
     }
 
+    /// true if this node is rendered below the node at id and depth
+    #[inline]
+    pub fn is_in_subtree(&self, id: &str, depth: usize) -> bool {
+        match self.0.try_borrow() {
+            Ok(node) => is_ancestor_id_of(id, &node.id) && (node.id != id || node.depth > depth),
+            Err(_) => false,
+        }
+    }
+
+    #[inline]
+    pub fn ptr_eq(&self, other: &Node) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+
+    /// sorts nodes after the nodes rendered below them
+    #[inline]
+    pub fn unmount_order(&self) -> (usize, usize) {
+        match self.0.try_borrow() {
+            Ok(node) => (node.id.matches('.').count(), node.depth),
+            Err(_) => (0, 0),
+        }
+    }
+
+    /// runs the unmount hooks of a node removed without unmounting it, `will_unmount` and
+    /// the cleanups of components, then their `did_unmount` with the lifecycles
+    #[inline]
+    pub fn unmount_removed(&self) {
+        if let Ok(node) = self.0.try_borrow() {
+            node.unmount_removed(self);
+        }
+    }
+
+    /// marks the updater of a component unmounted, for nodes removed without unmounting them
+    #[inline]
+    pub fn set_unmounted(&self) {
+        if let Ok(node) = self.0.try_borrow() {
+            if let NodeKind::Component { ref instance, .. } = node.kind {
+                instance.updater.set_unmounted();
+            }
+        }
+    }
+
+    #[inline]
+    pub fn portal_fragment(&self) -> Option<(String, View)> {
+        self.0
+            .try_borrow()
+            .ok()
+            .and_then(|node| node.portal_fragment())
+    }
+
     #[inline]
     pub fn mount(&self, transaction: &mut Transaction) -> View {
         self.as_mut().mount(transaction)
@@ -557,4 +1007,29 @@ impl Node {
     pub fn receive(&self, next_view: View, transaction: &mut Transaction) -> View {
         self.as_mut().receive(next_view, transaction)
     }
+    #[inline]
+    pub fn receive_with_context(
+        &self,
+        next_view: View,
+        parent_context: &Props,
+        parent_typed_context: &TypedContext,
+        transaction: &mut Transaction,
+    ) -> View {
+        self.as_mut().receive_with_context(
+            next_view,
+            parent_context,
+            parent_typed_context,
+            transaction,
+        )
+    }
+    #[inline]
+    pub fn update_context(
+        &self,
+        parent_context: &Props,
+        parent_typed_context: &TypedContext,
+        transaction: &mut Transaction,
+    ) -> View {
+        self.as_mut()
+            .update_context(parent_context, parent_typed_context, transaction)
+    }
 }
@@ -3,6 +3,7 @@ use std::cell::{Ref, RefCell, RefMut};
 
 use fnv::FnvHashMap;
 
+use super::super::is_ancestor_id_of;
 use super::Node;
 
 #[derive(Clone)]
@@ -41,6 +42,29 @@ impl Nodes {
         top_node
     }
 
+    /// removes every node rendered below the node at id and depth, returning the removed nodes
+    #[inline]
+    pub fn remove_subtree(&self, id: &str, depth: usize) -> Vec<Node> {
+        let mut removed = Vec::new();
+
+        self.as_mut().retain(|&(ref node_id, node_depth), node| {
+            if !is_ancestor_id_of(id, node_id) || (node_id == id && node_depth <= depth) {
+                true
+            } else {
+                removed.push(node.clone());
+                false
+            }
+        });
+
+        removed
+    }
+
+    /// removes every node, returning the removed nodes
+    #[inline]
+    pub fn clear(&self) -> Vec<Node> {
+        self.as_mut().drain().map(|(_, node)| node).collect()
+    }
+
     #[inline]
     pub fn get_at_depth(&self, id: String, depth: usize) -> Option<Node> {
         self.as_ref().get(&(id, depth)).map(Clone::clone)
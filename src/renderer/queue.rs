@@ -1,11 +1,14 @@
 use std::collections::LinkedList;
 use std::sync::{Arc, Mutex, MutexGuard};
 
-use super::super::{Props, View};
+use super::super::{Props, RawView, View};
 
 pub enum Message {
     Mount(View),
+    Render(View),
+    Hydrate(View, RawView),
     Update(String, usize, Box<dyn Fn(&Props) -> Props + Send>),
+    Timer(usize),
     Unmount,
 }
 
@@ -36,6 +39,14 @@ impl Queue {
         self.push(Message::Mount(view));
     }
     #[inline]
+    pub fn push_render(&self, view: View) {
+        self.push(Message::Render(view));
+    }
+    #[inline]
+    pub fn push_hydrate(&self, view: View, existing: RawView) {
+        self.push(Message::Hydrate(view, existing));
+    }
+    #[inline]
     pub fn push_update<F>(&self, id: String, depth: usize, f: F)
     where
         F: 'static + Send + Fn(&Props) -> Props,
@@ -43,6 +54,10 @@ impl Queue {
         self.push(Message::Update(id, depth, Box::new(f)))
     }
     #[inline]
+    pub fn push_timer(&self, timer_id: usize) {
+        self.push(Message::Timer(timer_id));
+    }
+    #[inline]
     pub fn push_unmount(&self) {
         self.push(Message::Unmount);
     }
@@ -51,4 +66,24 @@ impl Queue {
     pub fn pop(&self) -> Option<Message> {
         self.lock().pop_back()
     }
+
+    /// pops every update waiting at the front of the queue
+    #[inline]
+    pub fn pop_updates(&self) -> Vec<(String, usize, Box<dyn Fn(&Props) -> Props + Send>)> {
+        let mut lock = self.lock();
+        let mut updates = Vec::new();
+
+        loop {
+            match lock.back() {
+                Some(&Message::Update(_, _, _)) => (),
+                _ => break,
+            }
+
+            if let Some(Message::Update(id, depth, f)) = lock.pop_back() {
+                updates.push((id, depth, f));
+            }
+        }
+
+        updates
+    }
 }
@@ -1,22 +1,96 @@
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use messenger::Messenger;
 use serde_json::{to_value, Map, Value};
 
-use super::super::{EventManager, Props, Transaction, View};
-use super::{Message, Node, Nodes, Queue};
+use super::super::{
+    is_ancestor_id_of, parent_id, view_id, DispatchResult, Event, EventManager, Listener, Prop,
+    Props, RawView, Scheduler, Task, ThreadScheduler, Transaction, TypedContext, View,
+};
+use super::{
+    hydrate_raw_view, Lifecycle, Lifecycles, Message, Node, NodeInner, Nodes, Queue, Request,
+    Requests, RpcError, Subscriptions, TimerCallback, Timers,
+};
 
 static ROOT_ID: AtomicUsize = AtomicUsize::new(0);
 
+type Waker = Arc<Mutex<Option<Arc<dyn Fn() + Send + Sync>>>>;
+
+thread_local! {
+    /// how many renderer handlers this thread is running, the messenger keeps its handlers
+    /// locked while they run so they can not be added or removed then
+    static HANDLING: Cell<usize> = Cell::new(0);
+}
+
+struct Handling;
+
+impl Handling {
+    #[inline]
+    fn new() -> Self {
+        HANDLING.with(|handling| handling.set(handling.get() + 1));
+        Handling
+    }
+
+    #[inline]
+    fn is_active() -> bool {
+        HANDLING.with(|handling| handling.get() != 0)
+    }
+}
+
+impl Drop for Handling {
+    #[inline]
+    fn drop(&mut self) {
+        HANDLING.with(|handling| handling.set(handling.get() - 1));
+    }
+}
+
 pub struct RendererInner {
     root_id: String,
     root_index: usize,
     nodes: Nodes,
+    lifecycles: Lifecycles,
     messenger: Messenger<Value>,
     event_manager: EventManager,
     queue: Queue,
+    requests: Requests,
+    request_timeout: Mutex<Option<Duration>>,
+    subscriptions: Subscriptions,
+    scheduler: Arc<dyn Scheduler>,
+    timers: Timers,
     processing: AtomicBool,
+    subscribed: AtomicBool,
+    /// the names and ids of the messenger handlers the renderer added
+    handlers: Mutex<Vec<(&'static str, u32)>>,
+    waker: Waker,
+}
+
+impl RendererInner {
+    #[inline]
+    fn remove_handlers(&self) {
+        let mut handlers = self
+            .handlers
+            .lock()
+            .expect("failed to acquire handlers lock");
+
+        for (name, id) in handlers.drain(..) {
+            self.messenger.off(name, id);
+        }
+    }
+}
+
+impl Drop for RendererInner {
+    #[inline]
+    fn drop(&mut self) {
+        // dropped while a handler runs the handlers are left in place, they do nothing once
+        // the renderer is gone
+        if !Handling::is_active() {
+            self.remove_handlers();
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -28,25 +102,146 @@ unsafe impl Sync for Renderer {}
 impl Renderer {
     #[inline]
     pub fn new(view: View, event_manager: EventManager, messenger: Messenger<Value>) -> Self {
+        let renderer = Self::new_empty(event_manager, messenger);
+        renderer.mount(view);
+        renderer
+    }
+
+    /// creates a renderer without mounting a view
+    #[inline]
+    pub fn new_empty(event_manager: EventManager, messenger: Messenger<Value>) -> Self {
+        Self::with_scheduler(event_manager, messenger, ThreadScheduler::new())
+    }
+
+    /// creates a renderer without mounting a view, timers and frames are scheduled with
+    /// scheduler, see `set_waker` for how they are run
+    #[inline]
+    pub fn with_scheduler<S>(
+        event_manager: EventManager,
+        messenger: Messenger<Value>,
+        scheduler: S,
+    ) -> Self
+    where
+        S: 'static + Scheduler,
+    {
         let mut root_id = String::new();
         let root_index = ROOT_ID.fetch_add(1, Ordering::SeqCst);
 
         root_id.push('.');
         root_id.push_str(&root_index.to_string());
 
-        let renderer = Renderer(Arc::new(RendererInner {
+        Renderer(Arc::new(RendererInner {
             root_index: root_index,
             root_id: root_id,
             nodes: Nodes::new(),
+            lifecycles: Lifecycles::new(),
             messenger: messenger,
             event_manager: event_manager,
             queue: Queue::new(),
+            requests: Requests::new(),
+            request_timeout: Mutex::new(None),
+            subscriptions: Subscriptions::new(),
+            scheduler: Arc::new(scheduler),
+            timers: Timers::new(),
             processing: AtomicBool::new(false),
-        }));
+            subscribed: AtomicBool::new(false),
+            handlers: Mutex::new(Vec::new()),
+            waker: Arc::new(Mutex::new(None)),
+        }))
+    }
 
-        renderer.mount(view);
+    /// subscribes to the host messages when a view is mounted, renderers that never mount,
+    /// like the ones server rendering uses, do not answer hosts
+    #[inline]
+    fn subscribe(&self) {
+        self.0.subscribed.store(true, Ordering::SeqCst);
+        self.update_handlers();
+    }
+    /// unsubscribes from the host messages when the view is unmounted
+    #[inline]
+    fn unsubscribe(&self) {
+        self.0.subscribed.store(false, Ordering::SeqCst);
+        self.update_handlers();
+    }
 
-        renderer
+    /// adds or removes the messenger handlers, inside a handler this waits until the next
+    /// mount or unmount, the handlers ignore messages while the renderer is unsubscribed
+    #[inline]
+    fn update_handlers(&self) {
+        if Handling::is_active() {
+            return;
+        }
+
+        if self.0.subscribed.load(Ordering::SeqCst) {
+            let mut handlers = self
+                .0
+                .handlers
+                .lock()
+                .expect("failed to acquire handlers lock");
+
+            if handlers.is_empty() {
+                handlers.push(("virtual_view.event", self.subscribe_events()));
+                handlers.push(("virtual_view.message", self.subscribe_messages()));
+            }
+        } else {
+            self.0.remove_handlers();
+        }
+    }
+
+    /// dispatches the `virtual_view.event` messages `{ id, name, data }` hosts send for views
+    /// below the root, answering with the dispatch result
+    #[inline]
+    fn subscribe_events(&self) -> u32 {
+        let renderer = Arc::downgrade(&self.0);
+
+        self.0.messenger.on("virtual_view.event", move |message| {
+            let _handling = Handling::new();
+            let renderer = match renderer.upgrade() {
+                Some(ref inner) if inner.subscribed.load(Ordering::SeqCst) => {
+                    Renderer(inner.clone())
+                }
+                _ => return None,
+            };
+            let id = message.get("id").and_then(Value::as_str)?;
+
+            if !is_ancestor_id_of(&renderer.0.root_id, id) {
+                return None;
+            }
+
+            let name = message.get("name").and_then(Value::as_str)?;
+
+            let mut event = match message.get("data") {
+                Some(&Value::Object(ref data)) => data
+                    .iter()
+                    .map(|(k, v)| (k.clone(), Prop::from(v)))
+                    .collect(),
+                _ => Props::new(),
+            };
+            event.set("name", name);
+
+            Some(renderer.dispatch(id, &mut event).to_json())
+        })
+    }
+
+    /// broadcasts the `virtual_view.message` messages `{ name, data }` hosts send to the
+    /// components subscribed to name
+    #[inline]
+    fn subscribe_messages(&self) -> u32 {
+        let renderer = Arc::downgrade(&self.0);
+
+        self.0.messenger.on("virtual_view.message", move |message| {
+            let _handling = Handling::new();
+            let renderer = match renderer.upgrade() {
+                Some(ref inner) if inner.subscribed.load(Ordering::SeqCst) => {
+                    Renderer(inner.clone())
+                }
+                _ => return None,
+            };
+            let name = message.get("name").and_then(Value::as_str)?;
+
+            renderer.broadcast(name, message.get("data").unwrap_or(&Value::Null));
+            None
+        })
     }
 
     #[inline]
@@ -57,6 +252,37 @@ impl Renderer {
     pub fn root_index(&self) -> usize {
         self.0.root_index
     }
+    /// the view currently shown by the host, None if nothing is mounted
+    #[inline]
+    pub fn snapshot(&self) -> Option<RawView> {
+        self.snapshot_at(&self.0.root_id)
+    }
+
+    /// the view currently shown by the host at id
+    #[inline]
+    pub fn snapshot_at(&self, id: &str) -> Option<RawView> {
+        if let Some(node) = self.0.nodes.get(id.into()) {
+            return Some((&node.rendered_view()).into());
+        }
+
+        // text views have no node of their own, so look them up in their parent
+        let parent_id = parent_id(id);
+
+        match self.0.nodes.get(parent_id.clone()) {
+            Some(node) => node
+                .rendered_view()
+                .children()
+                .and_then(|children| {
+                    children
+                        .iter()
+                        .enumerate()
+                        .find(|&(index, child)| view_id(&parent_id, child.key(), index) == id)
+                })
+                .map(|(_, child)| child.into()),
+            None => None,
+        }
+    }
+
     #[inline]
     pub fn event_manager(&self) -> &EventManager {
         &self.0.event_manager
@@ -65,6 +291,60 @@ impl Renderer {
     pub(super) fn nodes(&self) -> &Nodes {
         &self.0.nodes
     }
+    #[inline]
+    pub(super) fn lifecycles(&self) -> &Lifecycles {
+        &self.0.lifecycles
+    }
+    #[inline]
+    pub(super) fn requests(&self) -> &Requests {
+        &self.0.requests
+    }
+    #[inline]
+    pub(super) fn subscriptions(&self) -> &Subscriptions {
+        &self.0.subscriptions
+    }
+    #[inline]
+    pub fn scheduler(&self) -> &dyn Scheduler {
+        &*self.0.scheduler
+    }
+
+    /// the timeout of requests sent without one, None by default so requests only schedule
+    /// a timeout when one is set
+    #[inline]
+    pub fn request_timeout(&self) -> Option<Duration> {
+        *self
+            .0
+            .request_timeout
+            .lock()
+            .expect("failed to acquire request timeout lock")
+    }
+    /// sets the timeout of requests sent without one, None waits until the host answers
+    #[inline]
+    pub fn set_request_timeout(&self, timeout: Option<Duration>) {
+        *self
+            .0
+            .request_timeout
+            .lock()
+            .expect("failed to acquire request timeout lock") = timeout;
+    }
+
+    /// sets the function the scheduler calls, from its own thread, after it queued a timer
+    /// or frame, hosts use it to wake the thread the renderer runs on and call `process`
+    /// there, without one queued timers run with the next render, update or event
+    #[inline]
+    pub fn set_waker<F>(&self, f: F)
+    where
+        F: 'static + Fn() + Send + Sync,
+    {
+        *self.0.waker.lock().expect("failed to acquire waker lock") = Some(Arc::new(f));
+    }
+
+    /// runs the queued timers, frames and updates, must be called on the thread the renderer
+    /// runs on
+    #[inline]
+    pub fn process(&self) {
+        self.process_queue();
+    }
 
     #[inline]
     fn processing(&self) -> bool {
@@ -82,23 +362,166 @@ impl Renderer {
     #[inline]
     fn process_queue(&self) {
         if self.processing() {
-            if let Some(message) = self.0.queue.pop() {
-                match message {
-                    Message::Mount(view) => self.internal_mount(view),
-                    Message::Update(id, depth, f) => self.internal_update(id, depth, f),
-                    Message::Unmount => self.internal_unmount(),
+            while let Some(message) = self.0.queue.pop() {
+                let host_mounted = match &message {
+                    &Message::Mount(_) | &Message::Hydrate(_, _) => false,
+                    &Message::Render(_) => !self.0.nodes.is_empty(),
+                    _ => true,
+                };
+
+                if let Err(payload) =
+                    panic::catch_unwind(AssertUnwindSafe(|| self.process_message(message)))
+                {
+                    // a panicking unmount hook must not leave the renderer processing
+                    let _ = panic::catch_unwind(AssertUnwindSafe(|| self.recover(host_mounted)));
+                    self.0.processing.store(false, Ordering::SeqCst);
+                    panic::resume_unwind(payload);
+                }
+            }
+            self.0.processing.store(false, Ordering::SeqCst);
+        }
+    }
+
+    #[inline]
+    fn process_message(&self, message: Message) {
+        match message {
+            Message::Mount(view) => self.internal_mount(view),
+            Message::Render(view) => self.internal_render(view),
+            Message::Hydrate(view, existing) => self.internal_hydrate(view, existing),
+            Message::Update(id, depth, f) => {
+                let mut updates = vec![(id, depth, f)];
+                updates.extend(self.0.queue.pop_updates());
+                self.internal_update(updates)
+            }
+            Message::Timer(timer_id) => {
+                if let Some(callback) = self.0.timers.take_callback(timer_id) {
+                    callback();
                 }
+            }
+            Message::Unmount => self.internal_unmount(),
+        }
+    }
+
+    /// tears down the whole tree after an error no component caught, so the renderer stays usable
+    #[inline]
+    fn recover(&self, host_mounted: bool) {
+        let mut transaction = Transaction::new();
+        let root_view = match self.0.nodes.get(self.0.root_id.clone()) {
+            Some(ref node) if host_mounted => Some(node.rendered_view()),
+            _ => None,
+        };
+
+        let host_transaction = if host_mounted {
+            Some(&mut transaction)
+        } else {
+            None
+        };
+        self.clear_subtree(&self.0.root_id, 0, host_transaction);
+
+        // the root component is not below the root
+        for node in self.0.nodes.clear() {
+            if let (true, Some((id, view))) = (host_mounted, node.portal_fragment()) {
+                transaction.remove(&id, view.into());
+            }
+            if self.0.lifecycles.remove_node(&node) {
+                node.set_unmounted();
+            } else {
+                node.unmount_removed();
+            }
+        }
+        self.0.requests.cancel(&self.0.root_id, 0);
+        self.0.subscriptions.remove_component(&self.0.root_id, 0);
+        self.clear_timers(&self.0.root_id, 0);
+
+        if let Some(view) = root_view {
+            transaction.unmount(&self.0.root_id, view.into());
+        }
+        if !transaction.is_empty() {
+            self.handle_transaction(transaction);
+        }
+        self.run_lifecycles();
+    }
+
+    /// removes the nodes, lifecycles, event handlers, requests, subscriptions and timers below
+    /// the node at id and depth, running the unmount hooks of the removed nodes, if the host
+    /// has the subtree the removed event handlers and the fragments portals rendered into
+    /// other containers are removed in transaction
+    #[inline]
+    pub(super) fn clear_subtree(
+        &self,
+        id: &str,
+        depth: usize,
+        transaction: Option<&mut Transaction>,
+    ) {
+        self.0.event_manager.write().remove_portals(id);
+
+        let mut nodes = self.0.nodes.remove_subtree(id, depth);
+        let mounting = self.0.lifecycles.remove_subtree(id, depth);
+
+        // children before parents, like unmount, nodes that never mounted are not unmounted
+        nodes.sort_by(|a, b| b.unmount_order().cmp(&a.unmount_order()));
+
+        for node in nodes.iter() {
+            if mounting.iter().any(|mounting| mounting.ptr_eq(node)) {
+                node.set_unmounted();
             } else {
-                self.0.processing.store(false, Ordering::SeqCst);
+                node.unmount_removed();
+            }
+        }
+        self.0.requests.cancel_subtree(id, depth);
+        self.0.subscriptions.remove_subtree(id, depth);
+
+        for scheduler_id in self.0.timers.remove_subtree(id, depth) {
+            self.0.scheduler.clear(scheduler_id);
+        }
+        let removed_events = self.0.event_manager.write().remove_subtree(id);
+
+        if let Some(transaction) = transaction {
+            for (id, view) in nodes.iter().filter_map(Node::portal_fragment) {
+                transaction.remove(&id, view.into());
+            }
+            for (id, name) in removed_events {
+                transaction.remove_event(&id, &name);
             }
         }
     }
 
+    /// sends the transaction, with the removes of the once handlers called since the last one
     #[inline(always)]
-    fn handle_transaction(&self, transaction: Transaction) {
+    fn handle_transaction(&self, mut transaction: Transaction) {
+        let removed_once = self
+            .0
+            .event_manager
+            .write()
+            .take_removed_once(&self.0.root_id);
+
+        for (id, name) in removed_once {
+            transaction.remove_event(&id, &name);
+        }
         self.send_no_callback("virtual_view.transaction", to_value(transaction).unwrap());
     }
 
+    /// tells the host about once handlers a dispatch removed if no transaction did
+    #[inline]
+    fn flush_removed_once(&self) {
+        let has_removed = self
+            .0
+            .event_manager
+            .read()
+            .has_removed_once(&self.0.root_id);
+
+        if has_removed {
+            self.handle_transaction(Transaction::new());
+        }
+    }
+
+    #[inline]
+    fn run_lifecycles(&self) {
+        for lifecycle in self.0.lifecycles.take() {
+            lifecycle.run();
+        }
+    }
+
     #[inline]
     pub fn send<N, V, F>(&self, name: N, json: V, f: F)
     where
@@ -120,6 +543,139 @@ impl Renderer {
         });
     }
 
+    /// sends a message for the component at id and depth expecting one response, resolving
+    /// with the first answer as it is, the request fails if the host answers with an
+    /// `error` key, does not answer before the timeout or the component unmounts
+    #[inline]
+    pub(super) fn request<N, V>(
+        &self,
+        id: &str,
+        depth: usize,
+        name: N,
+        json: V,
+        timeout: Option<Duration>,
+    ) -> Request
+    where
+        N: Into<String>,
+        V: Into<Value>,
+    {
+        let (pending, request) = self.0.requests.push(id.to_owned(), depth);
+
+        if let Some(timeout) = timeout {
+            let timeout_pending = pending.clone();
+
+            let scheduler_id = self.0.scheduler.set_timeout(
+                timeout,
+                Box::new(move || {
+                    timeout_pending.resolve(Err(RpcError::Timeout));
+                }),
+            );
+            pending.set_timeout(self.0.scheduler.clone(), scheduler_id);
+        }
+
+        // the first answer is the response, it is not merged with others like `send` does
+        let _ = self
+            .0
+            .messenger
+            .send(name, json.into(), move |mut responses| {
+                let response = if responses.is_empty() {
+                    Value::Null
+                } else {
+                    responses.swap_remove(0)
+                };
+                pending.respond(response);
+            });
+
+        request
+    }
+
+    /// calls callback through the queue once after delay, unless the component at id and
+    /// depth unmounts first, returns the timer id
+    #[inline]
+    pub(super) fn set_timeout(
+        &self,
+        id: &str,
+        depth: usize,
+        delay: Duration,
+        callback: TimerCallback,
+    ) -> usize {
+        self.schedule(id, depth, false, callback, |scheduler, task| {
+            scheduler.set_timeout(delay, task)
+        })
+    }
+    /// calls callback through the queue every interval until the component at id and depth
+    /// unmounts, returns the timer id
+    #[inline]
+    pub(super) fn set_interval(
+        &self,
+        id: &str,
+        depth: usize,
+        interval: Duration,
+        callback: TimerCallback,
+    ) -> usize {
+        self.schedule(id, depth, true, callback, |scheduler, task| {
+            scheduler.set_interval(interval, task)
+        })
+    }
+    /// calls callback through the queue before the next frame, unless the component at id
+    /// and depth unmounts first, returns the timer id
+    #[inline]
+    pub(super) fn request_frame(&self, id: &str, depth: usize, callback: TimerCallback) -> usize {
+        self.schedule(id, depth, false, callback, |scheduler, task| {
+            scheduler.request_frame(task)
+        })
+    }
+
+    #[inline]
+    pub(super) fn clear_timer(&self, timer_id: usize) {
+        if let Some(scheduler_id) = self.0.timers.remove(timer_id) {
+            self.0.scheduler.clear(scheduler_id);
+        }
+    }
+    /// clears the timers of the component at id and depth
+    #[inline]
+    pub(super) fn clear_timers(&self, id: &str, depth: usize) {
+        for scheduler_id in self.0.timers.remove_component(id, depth) {
+            self.0.scheduler.clear(scheduler_id);
+        }
+    }
+
+    #[inline]
+    fn schedule<F>(
+        &self,
+        id: &str,
+        depth: usize,
+        repeat: bool,
+        callback: TimerCallback,
+        f: F,
+    ) -> usize
+    where
+        F: FnOnce(&dyn Scheduler, Task) -> usize,
+    {
+        let timer_id = self.0.timers.next_id();
+        let queue = self.0.queue.clone();
+        let waker = self.0.waker.clone();
+
+        self.0
+            .timers
+            .add(timer_id, id.to_owned(), depth, repeat, callback);
+
+        let scheduler_id = f(
+            &*self.0.scheduler,
+            Box::new(move || {
+                queue.push_timer(timer_id);
+
+                let waker = waker.lock().expect("failed to acquire waker lock").clone();
+                if let Some(waker) = waker {
+                    waker();
+                }
+            }),
+        );
+        self.0.timers.set_scheduler_id(timer_id, scheduler_id);
+
+        timer_id
+    }
+
     #[inline]
     pub fn send_no_callback<N, V>(&self, name: N, json: V)
     where
@@ -129,6 +685,54 @@ impl Renderer {
         let _ = self.0.messenger.send_no_callback(name, json.into());
     }
 
+    /// runs f with queued updates held back, so every update it triggers renders in one transaction
+    #[inline]
+    pub fn batch<F, T>(&self, f: F) -> T
+    where
+        F: FnOnce() -> T,
+    {
+        if self.processing() {
+            // a panicking handler must not leave the renderer processing for good
+            let result = panic::catch_unwind(AssertUnwindSafe(f));
+            self.finish_processing();
+
+            match result {
+                Ok(out) => out,
+                Err(payload) => panic::resume_unwind(payload),
+            }
+        } else {
+            f()
+        }
+    }
+
+    /// calls the handlers components subscribed to name with data, batching the updates
+    /// they trigger
+    #[inline]
+    pub fn broadcast(&self, name: &str, data: &Value) {
+        let handlers = self.0.subscriptions.handlers(name);
+
+        self.batch(|| {
+            for handler in handlers {
+                handler(data);
+            }
+        });
+    }
+
+    /// dispatches the event through the event manager, batching the updates its handlers trigger
+    #[inline]
+    pub fn dispatch(&self, id: &str, event: &mut Props) -> DispatchResult {
+        let result = self.batch(|| self.0.event_manager.dispatch(id, event));
+        self.flush_removed_once();
+        result
+    }
+    /// dispatches a typed event, see `dispatch`
+    #[inline]
+    pub fn dispatch_event(&self, id: &str, event: &mut Event) -> DispatchResult {
+        let result = self.batch(|| self.0.event_manager.dispatch_event(id, event));
+        self.flush_removed_once();
+        result
+    }
+
     #[inline]
     pub fn mount(&self, view: View) {
         if !self.0.nodes.is_empty() {
@@ -138,6 +742,24 @@ impl Renderer {
         self.process_queue();
     }
 
+    /// renders the view at the root, reconciling it with the mounted view so only the changes
+    /// are sent and component state is kept
+    #[inline]
+    pub fn render(&self, view: View) {
+        self.0.queue.push_render(view);
+        self.process_queue();
+    }
+
+    /// mounts the view over an existing server rendered view, only patching mismatches
+    #[inline]
+    pub fn hydrate(&self, view: View, existing: RawView) {
+        if !self.0.nodes.is_empty() {
+            self.unmount();
+        }
+        self.0.queue.push_hydrate(view, existing);
+        self.process_queue();
+    }
+
     #[inline]
     pub fn unmount(&self) {
         self.0.queue.push_unmount();
@@ -155,6 +777,8 @@ impl Renderer {
 
     #[inline]
     fn internal_mount(&self, view: View) {
+        self.subscribe();
+
         let mut transaction = Transaction::new();
         let node = Node::new(
             self.0.root_index,
@@ -163,6 +787,7 @@ impl Renderer {
             self,
             view,
             &Props::new(),
+            &TypedContext::new(),
         );
 
         let view = node.mount(&mut transaction);
@@ -170,7 +795,74 @@ impl Renderer {
 
         self.handle_transaction(transaction);
 
-        self.finish_processing();
+        self.run_lifecycles();
+    }
+
+    #[inline]
+    fn internal_render(&self, view: View) {
+        let node = match self.0.nodes.get(self.0.root_id.clone()) {
+            Some(node) => node,
+            None => return self.internal_mount(view),
+        };
+        let mut transaction = Transaction::new();
+
+        if NodeInner::should_update(&node.as_ref().view, &view) {
+            node.receive(view, &mut transaction);
+        } else {
+            let prev_view = node.unmount(&mut transaction);
+            let node = Node::new(
+                self.0.root_index,
+                0,
+                self.0.root_id.clone(),
+                self,
+                view,
+                &Props::new(),
+                &TypedContext::new(),
+            );
+            let next_view = node.mount(&mut transaction);
+            transaction.replace(&self.0.root_id, prev_view.into(), next_view.into());
+        }
+
+        if !transaction.is_empty() {
+            self.handle_transaction(transaction);
+        }
+
+        self.run_lifecycles();
+    }
+
+    #[inline]
+    fn internal_hydrate(&self, view: View, existing: RawView) {
+        self.subscribe();
+
+        let mut transaction = Transaction::new();
+        let mut mismatches = Vec::new();
+        let node = Node::new(
+            self.0.root_index,
+            0,
+            self.0.root_id.clone(),
+            self,
+            view,
+            &Props::new(),
+            &TypedContext::new(),
+        );
+
+        let view = node.mount(&mut transaction);
+        hydrate_raw_view(
+            &self.0.root_id,
+            &existing,
+            &view.into(),
+            &mut transaction,
+            &mut mismatches,
+        );
+
+        if !transaction.is_empty() {
+            self.handle_transaction(transaction);
+        }
+        if !mismatches.is_empty() {
+            self.send_no_callback("virtual_view.mismatches", to_value(mismatches).unwrap());
+        }
+
+        self.run_lifecycles();
     }
 
     #[inline]
@@ -188,22 +880,44 @@ impl Renderer {
             self.handle_transaction(transaction);
         }
 
-        self.finish_processing();
+        self.unsubscribe();
+        self.run_lifecycles();
     }
 
     #[inline]
-    fn internal_update(&self, id: String, depth: usize, f: Box<dyn Fn(&Props) -> Props + Send>) {
-        if let Some(node) = self.0.nodes.get_at_depth(id, depth) {
-            let mut transaction = Transaction::new();
+    fn internal_update(&self, updates: Vec<(String, usize, Box<dyn Fn(&Props) -> Props + Send>)>) {
+        let mut transaction = Transaction::new();
+        let mut keys: Vec<(String, usize)> = Vec::with_capacity(updates.len());
+
+        for (id, depth, f) in updates {
+            if let Some(node) = self.0.nodes.get_at_depth(id.clone(), depth) {
+                node.as_mut().set_next_state(&*f);
+
+                let key = (id, depth);
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+        }
+
+        // ancestors update first, descendants they render consume their queued state as well
+        keys.sort_by_key(|&(ref id, depth)| (id.matches('.').count(), depth));
 
-            node.as_mut().update_state(&*f, &mut transaction);
+        for (id, depth) in keys {
+            if let Some(node) = self.0.nodes.get_at_depth(id, depth) {
+                let has_next_state = node.as_ref().has_next_state();
 
-            if !transaction.is_empty() {
-                self.handle_transaction(transaction);
+                if has_next_state {
+                    node.as_mut().update_next_state(&mut transaction);
+                }
             }
         }
 
-        self.finish_processing();
+        if !transaction.is_empty() {
+            self.handle_transaction(transaction);
+        }
+
+        self.run_lifecycles();
     }
 
     #[inline]
@@ -216,11 +930,9 @@ impl Renderer {
         let mut event_manager = self.0.event_manager.write();
 
         for (k, v) in props {
-            if k.starts_with("on") {
-                if let Some(f) = v.function() {
-                    transaction.add_event(id, k);
-                    event_manager.add(id, k, f.clone());
-                }
+            if let Some((listener, f)) = Listener::from_prop(k, v) {
+                transaction.add_listener(id, k, listener.clone());
+                event_manager.add(id, k, listener, f);
             }
         }
     }
@@ -235,11 +947,9 @@ impl Renderer {
         let mut event_manager = self.0.event_manager.write();
 
         for (k, v) in props {
-            if k.starts_with("on") {
-                if let Some(_) = v.function() {
-                    transaction.remove_event(id, k);
-                    event_manager.remove(id, k);
-                }
+            if k.starts_with("on") && v.handler().is_some() {
+                transaction.remove_event(id, k);
+                event_manager.remove(id, k);
             }
         }
     }
@@ -255,24 +965,53 @@ impl Renderer {
         let mut event_manager = self.0.event_manager.write();
 
         for (k, v) in next_props {
-            if k.starts_with("on") {
-                if let Some(f) = v.function() {
-                    if !prev_props.has(k) {
-                        transaction.add_event(id, k);
-                        event_manager.add(id, k, f.clone());
+            if let Some((listener, f)) = Listener::from_prop(k, v) {
+                match Listener::from_prop(k, prev_props.get(k)) {
+                    Some((ref prev_listener, _)) if prev_listener == &listener => {
+                        // the host already listens, only the handler changes
+                        event_manager.replace(id, k, f);
+                    }
+                    _ => {
+                        transaction.add_listener(id, k, listener.clone());
+                        event_manager.add(id, k, listener, f);
                     }
                 }
             }
         }
         for (k, v) in prev_props {
-            if k.starts_with("on") {
-                if let Some(_) = v.function() {
-                    if !next_props.has(k) {
-                        transaction.remove_event(id, k);
-                        event_manager.remove(id, k);
-                    }
+            if k.starts_with("on") && v.handler().is_some() {
+                if next_props.get(k).handler().is_none() {
+                    transaction.remove_event(id, k);
+                    event_manager.remove(id, k);
                 }
             }
         }
     }
+
+    /// calls the view's `ref` prop with its id after the transaction is sent
+    #[inline]
+    pub(super) fn mount_props_ref(&self, id: &str, props: &Props) {
+        if let Some(f) = props.get("ref").function() {
+            self.0
+                .lifecycles
+                .push(Lifecycle::Ref(id.into(), f.clone(), true));
+        }
+    }
+
+    #[inline]
+    pub(super) fn unmount_props_ref(&self, id: &str, props: &Props) {
+        if let Some(f) = props.get("ref").function() {
+            self.0
+                .lifecycles
+                .push(Lifecycle::Ref(id.into(), f.clone(), false));
+        }
+    }
+
+    #[inline]
+    pub(super) fn update_props_ref(&self, id: &str, prev_props: &Props, next_props: &Props) {
+        if prev_props.get("ref") != next_props.get("ref") {
+            self.unmount_props_ref(id, prev_props);
+            self.mount_props_ref(id, next_props);
+        }
+    }
 }
@@ -0,0 +1,211 @@
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
+
+use fnv::FnvHashMap;
+use futures::sync::oneshot;
+use futures::{Async, Future, Poll};
+use serde_json::Value;
+
+use super::super::{is_ancestor_id_of, Scheduler};
+use super::RpcError;
+
+type Sender = oneshot::Sender<Result<Value, RpcError>>;
+type PendingRequests = Mutex<FnvHashMap<(String, usize), Vec<PendingRequest>>>;
+
+/// a future resolving with the host's response to a request
+pub struct Request(oneshot::Receiver<Result<Value, RpcError>>);
+
+impl Future for Request {
+    type Item = Value;
+    type Error = RpcError;
+
+    #[inline]
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        match self.0.poll() {
+            Ok(Async::Ready(Ok(value))) => Ok(Async::Ready(value)),
+            Ok(Async::Ready(Err(error))) => Err(error),
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(_) => Err(RpcError::Cancelled),
+        }
+    }
+}
+
+struct PendingRequestInner {
+    sender: Option<Sender>,
+    timeout: Option<(Arc<dyn Scheduler>, usize)>,
+    key: (String, usize),
+    requests: Weak<PendingRequests>,
+}
+
+/// the sending half of a `Request`, only the first result it is resolved with is used
+#[derive(Clone)]
+pub struct PendingRequest(Arc<Mutex<PendingRequestInner>>);
+
+impl PendingRequest {
+    #[inline]
+    fn lock(&self) -> MutexGuard<PendingRequestInner> {
+        self.0.lock().expect("failed to acquire request lock")
+    }
+
+    /// the scheduler timeout of the request, cleared when the request resolves
+    #[inline]
+    pub fn set_timeout(&self, scheduler: Arc<dyn Scheduler>, scheduler_id: usize) {
+        let mut inner = self.lock();
+
+        if inner.sender.is_some() {
+            inner.timeout = Some((scheduler, scheduler_id));
+        } else {
+            drop(inner);
+            scheduler.clear(scheduler_id);
+        }
+    }
+
+    /// resolves the request, clears its timeout and removes it from the component's
+    /// requests, returns false if it was already resolved
+    #[inline]
+    pub fn resolve(&self, result: Result<Value, RpcError>) -> bool {
+        let (sender, timeout) = {
+            let mut inner = self.lock();
+            (inner.sender.take(), inner.timeout.take())
+        };
+
+        if let Some((scheduler, scheduler_id)) = timeout {
+            scheduler.clear(scheduler_id);
+        }
+
+        match sender {
+            Some(sender) => {
+                self.remove();
+                let _ = sender.send(result);
+                true
+            }
+            None => false,
+        }
+    }
+
+    #[inline]
+    fn remove(&self) {
+        let (key, requests) = {
+            let inner = self.lock();
+            (inner.key.clone(), inner.requests.upgrade())
+        };
+        let requests = match requests {
+            Some(requests) => requests,
+            None => return,
+        };
+        let mut requests = requests.lock().expect("failed to acquire requests lock");
+
+        let is_empty = match requests.get_mut(&key) {
+            Some(component_requests) => {
+                component_requests.retain(|pending| !Arc::ptr_eq(&pending.0, &self.0));
+                component_requests.is_empty()
+            }
+            None => false,
+        };
+
+        if is_empty {
+            requests.remove(&key);
+        }
+    }
+
+    /// resolves the request with a host response, responses with a non null `error` key
+    /// fail with `RpcError::Host`
+    #[inline]
+    pub fn respond(&self, response: Value) -> bool {
+        let error = match response.get("error") {
+            Some(error) if !error.is_null() => Some(error.clone()),
+            _ => None,
+        };
+
+        self.resolve(match error {
+            Some(error) => Err(RpcError::Host(error)),
+            None => Ok(response),
+        })
+    }
+}
+
+/// the pending requests of every component by id and depth, requests remove themselves
+/// when they resolve
+#[derive(Clone)]
+pub struct Requests(Arc<PendingRequests>);
+
+impl Requests {
+    #[inline]
+    pub fn new() -> Self {
+        Requests(Arc::new(Mutex::new(FnvHashMap::default())))
+    }
+
+    #[inline]
+    fn lock(&self) -> MutexGuard<FnvHashMap<(String, usize), Vec<PendingRequest>>> {
+        self.0.lock().expect("failed to acquire requests lock")
+    }
+
+    /// creates a request for the component at id and depth
+    #[inline]
+    pub fn push(&self, id: String, depth: usize) -> (PendingRequest, Request) {
+        let (sender, receiver) = oneshot::channel();
+        let key = (id, depth);
+        let pending = PendingRequest(Arc::new(Mutex::new(PendingRequestInner {
+            sender: Some(sender),
+            timeout: None,
+            key: key.clone(),
+            requests: Arc::downgrade(&self.0),
+        })));
+
+        self.lock()
+            .entry(key)
+            .or_insert_with(Vec::new)
+            .push(pending.clone());
+
+        (pending, Request(receiver))
+    }
+
+    /// cancels the requests of the component at id and depth
+    #[inline]
+    pub fn cancel(&self, id: &str, depth: usize) {
+        let pending = self.lock().remove(&(id.to_owned(), depth));
+
+        for pending in pending.unwrap_or_default() {
+            pending.resolve(Err(RpcError::Cancelled));
+        }
+    }
+
+    /// cancels the requests of the components below the component at id and depth
+    #[inline]
+    pub fn cancel_subtree(&self, id: &str, depth: usize) {
+        let mut cancelled = Vec::new();
+
+        self.lock()
+            .retain(|&(ref request_id, request_depth), pending| {
+                if is_ancestor_id_of(id, request_id) && (request_id != id || request_depth > depth)
+                {
+                    cancelled.extend(pending.drain(..));
+                    false
+                } else {
+                    true
+                }
+            });
+
+        for pending in cancelled {
+            pending.resolve(Err(RpcError::Cancelled));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_resolve_removes_request() {
+        let requests = Requests::new();
+        let (first, _first) = requests.push(".0".into(), 0);
+        let (second, _second) = requests.push(".0".into(), 0);
+
+        assert!(first.resolve(Ok(Value::Null)));
+        assert!(!first.resolve(Ok(Value::Null)));
+        assert_eq!(requests.lock()[&(".0".to_owned(), 0)].len(), 1);
+
+        assert!(second.respond(Value::Null));
+        assert!(requests.lock().is_empty());
+    }
+}
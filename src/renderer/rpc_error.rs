@@ -0,0 +1,55 @@
+use std::error::Error;
+use std::fmt;
+
+use serde_json::Value;
+
+/// the error a `Request` fails with
+#[derive(Debug, Clone, PartialEq)]
+pub enum RpcError {
+    /// the host answered with the value of an `error` key
+    Host(Value),
+    /// the host did not answer before the timeout
+    Timeout,
+    /// the component that sent the request unmounted before the host answered
+    Cancelled,
+}
+
+impl RpcError {
+    #[inline]
+    pub fn is_host(&self) -> bool {
+        match self {
+            &RpcError::Host(_) => true,
+            _ => false,
+        }
+    }
+    #[inline]
+    pub fn is_timeout(&self) -> bool {
+        self == &RpcError::Timeout
+    }
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self == &RpcError::Cancelled
+    }
+}
+
+impl fmt::Display for RpcError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            &RpcError::Host(ref error) => write!(f, "host error {}", error),
+            &RpcError::Timeout => write!(f, "request timed out"),
+            &RpcError::Cancelled => write!(f, "request cancelled"),
+        }
+    }
+}
+
+impl Error for RpcError {
+    #[inline]
+    fn description(&self) -> &str {
+        match self {
+            &RpcError::Host(_) => "host error",
+            &RpcError::Timeout => "request timed out",
+            &RpcError::Cancelled => "request cancelled",
+        }
+    }
+}
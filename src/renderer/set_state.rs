@@ -0,0 +1,65 @@
+use std::fmt;
+
+use super::super::{Prop, Props};
+use super::Updater;
+
+/// sets a single key of a component's state, returned by `Instance::use_state`
+#[derive(Clone)]
+pub struct SetState {
+    key: String,
+    init: Prop,
+    updater: Updater,
+}
+
+impl SetState {
+    #[inline]
+    pub(crate) fn new(key: String, init: Prop, updater: Updater) -> Self {
+        SetState {
+            key: key,
+            init: init,
+            updater: updater,
+        }
+    }
+
+    #[inline(always)]
+    pub fn key(&self) -> &String {
+        &self.key
+    }
+
+    #[inline]
+    pub fn set<V>(&self, value: V)
+    where
+        V: Into<Prop>,
+    {
+        let value = value.into();
+        self.update(move |_| value.clone());
+    }
+
+    /// sets the key to the result of f called with the key's current value
+    #[inline]
+    pub fn update<F>(&self, f: F)
+    where
+        F: 'static + Send + Fn(&Prop) -> Prop,
+    {
+        let key = self.key.clone();
+        let init = self.init.clone();
+
+        self.updater.set_state(move |state: &Props| {
+            let mut next_state = state.clone();
+            let value = if state.has(&key) {
+                f(state.get(&key))
+            } else {
+                f(&init)
+            };
+            next_state.set(key.clone(), value);
+            next_state
+        });
+    }
+}
+
+impl fmt::Debug for SetState {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SetState({}, {:?})", self.key, self.updater)
+    }
+}
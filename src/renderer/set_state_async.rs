@@ -0,0 +1,108 @@
+use futures::{Async, Future, Poll};
+
+use super::super::{Prop, Props};
+use super::Updater;
+
+/// a future setting the state of a component with the transform its future resolves with,
+/// it resolves without waiting for its future once the component unmounts
+pub struct SetStateAsync<T> {
+    updater: Updater,
+    future: T,
+}
+
+impl<T> SetStateAsync<T> {
+    #[inline]
+    pub fn new(updater: Updater, future: T) -> Self {
+        SetStateAsync {
+            updater: updater,
+            future: future,
+        }
+    }
+}
+
+impl<T, F> Future for SetStateAsync<T>
+where
+    T: Future<Item = F>,
+    F: 'static + Send + Fn(&Props) -> Props,
+{
+    type Item = ();
+    type Error = T::Error;
+
+    #[inline]
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        if !self.updater.is_mounted() {
+            return Ok(Async::Ready(()));
+        }
+
+        match self.future.poll() {
+            Ok(Async::Ready(f)) => {
+                if self.updater.is_mounted() {
+                    self.updater.set_state(f);
+                }
+                Ok(Async::Ready(()))
+            }
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(_) if !self.updater.is_mounted() => Ok(Async::Ready(())),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// a future setting the state of a component with the `loading` and `error` keys, see
+/// `Updater::load`
+pub struct LoadState<T> {
+    updater: Updater,
+    future: T,
+}
+
+impl<T> LoadState<T> {
+    #[inline]
+    pub fn new(updater: Updater, future: T) -> Self {
+        LoadState {
+            updater: updater,
+            future: future,
+        }
+    }
+}
+
+impl<T, F> Future for LoadState<T>
+where
+    T: Future<Item = F>,
+    T::Error: Into<Prop>,
+    F: 'static + Send + Fn(&Props) -> Props,
+{
+    type Item = ();
+    type Error = ();
+
+    #[inline]
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        if !self.updater.is_mounted() {
+            return Ok(Async::Ready(()));
+        }
+
+        let result = match self.future.poll() {
+            Ok(Async::Ready(f)) => Ok(f),
+            Ok(Async::NotReady) => return Ok(Async::NotReady),
+            Err(error) => Err(error.into()),
+        };
+
+        if self.updater.is_mounted() {
+            match result {
+                Ok(f) => self.updater.set_state(move |state| {
+                    let mut next_state = f(state);
+                    next_state.set("loading", false);
+                    next_state.set("error", Prop::Null);
+                    next_state
+                }),
+                Err(error) => self.updater.set_state(move |state| {
+                    let mut next_state = state.clone();
+                    next_state.set("loading", false);
+                    next_state.set("error", error.clone());
+                    next_state
+                }),
+            }
+        }
+
+        Ok(Async::Ready(()))
+    }
+}
@@ -0,0 +1,111 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use fnv::FnvHashMap;
+use serde_json::Value;
+
+use super::super::is_ancestor_id_of;
+
+pub type Handler = Arc<dyn Fn(&Value) + Send + Sync>;
+
+struct Subscriber {
+    subscription_id: usize,
+    id: String,
+    depth: usize,
+    handler: Handler,
+}
+
+pub struct SubscriptionsInner {
+    subscription_id: AtomicUsize,
+    subscribers: Mutex<FnvHashMap<String, Vec<Subscriber>>>,
+}
+
+/// the handlers components subscribed to host messages with by message name
+#[derive(Clone)]
+pub struct Subscriptions(Arc<SubscriptionsInner>);
+
+impl Subscriptions {
+    #[inline]
+    pub fn new() -> Self {
+        Subscriptions(Arc::new(SubscriptionsInner {
+            subscription_id: AtomicUsize::new(0),
+            subscribers: Mutex::new(FnvHashMap::default()),
+        }))
+    }
+
+    #[inline]
+    fn lock(&self) -> MutexGuard<FnvHashMap<String, Vec<Subscriber>>> {
+        self.0
+            .subscribers
+            .lock()
+            .expect("failed to acquire subscriptions lock")
+    }
+
+    /// subscribes the component at id and depth to messages with name, returns the
+    /// subscription id
+    #[inline]
+    pub fn add(&self, id: String, depth: usize, name: String, handler: Handler) -> usize {
+        let subscription_id = self.0.subscription_id.fetch_add(1, Ordering::SeqCst);
+
+        self.lock()
+            .entry(name)
+            .or_insert_with(Vec::new)
+            .push(Subscriber {
+                subscription_id: subscription_id,
+                id: id,
+                depth: depth,
+                handler: handler,
+            });
+
+        subscription_id
+    }
+
+    #[inline]
+    pub fn remove(&self, name: &str, subscription_id: usize) {
+        self.retain(|subscriber, subscriber_name| {
+            subscriber_name != name || subscriber.subscription_id != subscription_id
+        });
+    }
+
+    /// removes the subscriptions of the component at id and depth
+    #[inline]
+    pub fn remove_component(&self, id: &str, depth: usize) {
+        self.retain(|subscriber, _| subscriber.id != id || subscriber.depth != depth);
+    }
+
+    /// removes the subscriptions of the components below the component at id and depth
+    #[inline]
+    pub fn remove_subtree(&self, id: &str, depth: usize) {
+        self.retain(|subscriber, _| {
+            !(is_ancestor_id_of(id, &subscriber.id)
+                && (subscriber.id != id || subscriber.depth > depth))
+        });
+    }
+
+    /// the handlers subscribed to name, in the order they subscribed
+    #[inline]
+    pub fn handlers(&self, name: &str) -> Vec<Handler> {
+        self.lock()
+            .get(name)
+            .map(|subscribers| {
+                subscribers
+                    .iter()
+                    .map(|subscriber| subscriber.handler.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    #[inline]
+    fn retain<F>(&self, f: F)
+    where
+        F: Fn(&Subscriber, &str) -> bool,
+    {
+        let mut subscribers = self.lock();
+
+        subscribers.retain(|name, subscribers| {
+            subscribers.retain(|subscriber| f(subscriber, name));
+            !subscribers.is_empty()
+        });
+    }
+}
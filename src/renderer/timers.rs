@@ -0,0 +1,128 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use fnv::FnvHashMap;
+
+use super::super::is_ancestor_id_of;
+
+pub type Callback = Arc<dyn Fn() + Send + Sync>;
+
+struct Timer {
+    id: String,
+    depth: usize,
+    scheduler_id: Option<usize>,
+    repeat: bool,
+    callback: Callback,
+}
+
+pub struct TimersInner {
+    timer_id: AtomicUsize,
+    timers: Mutex<FnvHashMap<usize, Timer>>,
+}
+
+/// the timers and frames components scheduled by timer id
+#[derive(Clone)]
+pub struct Timers(Arc<TimersInner>);
+
+impl Timers {
+    #[inline]
+    pub fn new() -> Self {
+        Timers(Arc::new(TimersInner {
+            timer_id: AtomicUsize::new(0),
+            timers: Mutex::new(FnvHashMap::default()),
+        }))
+    }
+
+    #[inline]
+    fn lock(&self) -> MutexGuard<FnvHashMap<usize, Timer>> {
+        self.0.timers.lock().expect("failed to acquire timers lock")
+    }
+
+    /// the id of the next timer, known before the scheduler is asked for it
+    #[inline]
+    pub fn next_id(&self) -> usize {
+        self.0.timer_id.fetch_add(1, Ordering::SeqCst)
+    }
+
+    /// adds the timer before it is scheduled, so it can not fire before it is added
+    #[inline]
+    pub fn add(&self, timer_id: usize, id: String, depth: usize, repeat: bool, callback: Callback) {
+        self.lock().insert(
+            timer_id,
+            Timer {
+                id: id,
+                depth: depth,
+                scheduler_id: None,
+                repeat: repeat,
+                callback: callback,
+            },
+        );
+    }
+
+    #[inline]
+    pub fn set_scheduler_id(&self, timer_id: usize, scheduler_id: usize) {
+        if let Some(timer) = self.lock().get_mut(&timer_id) {
+            timer.scheduler_id = Some(scheduler_id);
+        }
+    }
+
+    /// the callback of the timer, timers that do not repeat are removed
+    #[inline]
+    pub fn take_callback(&self, timer_id: usize) -> Option<Callback> {
+        let mut timers = self.lock();
+
+        let repeat = match timers.get(&timer_id) {
+            Some(timer) => timer.repeat,
+            None => return None,
+        };
+
+        if repeat {
+            timers.get(&timer_id).map(|timer| timer.callback.clone())
+        } else {
+            timers.remove(&timer_id).map(|timer| timer.callback)
+        }
+    }
+
+    /// removes the timer, returning its scheduler id
+    #[inline]
+    pub fn remove(&self, timer_id: usize) -> Option<usize> {
+        self.lock()
+            .remove(&timer_id)
+            .and_then(|timer| timer.scheduler_id)
+    }
+
+    /// removes the timers of the component at id and depth, returning their scheduler ids
+    #[inline]
+    pub fn remove_component(&self, id: &str, depth: usize) -> Vec<usize> {
+        self.remove_where(|timer| timer.id == id && timer.depth == depth)
+    }
+
+    /// removes the timers of the components below the component at id and depth, returning
+    /// their scheduler ids
+    #[inline]
+    pub fn remove_subtree(&self, id: &str, depth: usize) -> Vec<usize> {
+        self.remove_where(|timer| {
+            is_ancestor_id_of(id, &timer.id) && (timer.id != id || timer.depth > depth)
+        })
+    }
+
+    #[inline]
+    fn remove_where<F>(&self, f: F) -> Vec<usize>
+    where
+        F: Fn(&Timer) -> bool,
+    {
+        let mut timers = self.lock();
+        let mut scheduler_ids = Vec::new();
+
+        timers.retain(|_, timer| {
+            if f(timer) {
+                scheduler_ids.extend(timer.scheduler_id);
+                false
+            } else {
+                true
+            }
+        });
+
+        scheduler_ids
+    }
+}
@@ -1,16 +1,20 @@
 use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::hash::{Hash, Hasher};
+use std::time::Duration;
 
+use futures::Future;
 use serde_json::Value;
 
-use super::super::Props;
-use super::Renderer;
+use super::super::{Prop, Props};
+use super::{LoadState, Renderer, Request, SetStateAsync};
 
 pub struct UpdaterInner {
     id: String,
     depth: usize,
     renderer: Renderer,
+    mounted: AtomicBool,
 }
 
 #[derive(Clone)]
@@ -26,9 +30,28 @@ impl Updater {
             id: id,
             depth: depth,
             renderer: renderer,
+            mounted: AtomicBool::new(true),
         }))
     }
 
+    #[inline(always)]
+    pub fn id(&self) -> &String {
+        &self.0.id
+    }
+    #[inline(always)]
+    pub fn depth(&self) -> usize {
+        self.0.depth
+    }
+    /// false once the component unmounted, a component mounted again gets a new updater
+    #[inline]
+    pub fn is_mounted(&self) -> bool {
+        self.0.mounted.load(Ordering::SeqCst)
+    }
+    #[inline]
+    pub(super) fn set_unmounted(&self) {
+        self.0.mounted.store(false, Ordering::SeqCst);
+    }
+
     #[inline]
     fn set_json_id<V>(&self, json: V) -> Value
     where
@@ -56,6 +79,33 @@ impl Updater {
         self.0.renderer.send(name, self.set_json_id(json), f)
     }
 
+    /// sends a message expecting one response with the renderer's request timeout
+    #[inline]
+    pub fn request<N, V>(&self, name: N, json: V) -> Request
+    where
+        N: Into<String>,
+        V: Into<Value>,
+    {
+        let timeout = self.0.renderer.request_timeout();
+        self.request_timeout(name, json, timeout)
+    }
+
+    /// sends a message expecting one response before the timeout
+    #[inline]
+    pub fn request_timeout<N, V>(&self, name: N, json: V, timeout: Option<Duration>) -> Request
+    where
+        N: Into<String>,
+        V: Into<Value>,
+    {
+        self.0.renderer.request(
+            &self.0.id,
+            self.0.depth,
+            name,
+            self.set_json_id(json),
+            timeout,
+        )
+    }
+
     #[inline]
     pub fn send_no_callback<N, V>(&self, name: N, json: V)
     where
@@ -67,6 +117,62 @@ impl Updater {
             .send_no_callback(name, self.set_json_id(json))
     }
 
+    /// calls f with the data of every host message with name until the component unmounts,
+    /// returns the subscription id
+    #[inline]
+    pub fn subscribe<N, F>(&self, name: N, f: F) -> usize
+    where
+        N: Into<String>,
+        F: 'static + Send + Sync + Fn(&Value),
+    {
+        self.0.renderer.subscriptions().add(
+            self.0.id.clone(),
+            self.0.depth,
+            name.into(),
+            Arc::new(f),
+        )
+    }
+
+    #[inline]
+    pub fn unsubscribe(&self, name: &str, subscription_id: usize) {
+        self.0
+            .renderer
+            .subscriptions()
+            .remove(name, subscription_id)
+    }
+
+    #[inline]
+    pub fn set_timeout<F>(&self, delay: Duration, f: F) -> usize
+    where
+        F: 'static + Send + Sync + Fn(),
+    {
+        self.0
+            .renderer
+            .set_timeout(&self.0.id, self.0.depth, delay, Arc::new(f))
+    }
+    #[inline]
+    pub fn set_interval<F>(&self, interval: Duration, f: F) -> usize
+    where
+        F: 'static + Send + Sync + Fn(),
+    {
+        self.0
+            .renderer
+            .set_interval(&self.0.id, self.0.depth, interval, Arc::new(f))
+    }
+    #[inline]
+    pub fn request_frame<F>(&self, f: F) -> usize
+    where
+        F: 'static + Send + Sync + Fn(),
+    {
+        self.0
+            .renderer
+            .request_frame(&self.0.id, self.0.depth, Arc::new(f))
+    }
+    #[inline]
+    pub fn clear_timer(&self, timer_id: usize) {
+        self.0.renderer.clear_timer(timer_id)
+    }
+
     #[inline]
     pub fn set_state<F>(&self, f: F)
     where
@@ -75,6 +181,36 @@ impl Updater {
         self.0.renderer.update(self.0.id.clone(), self.0.depth, f)
     }
 
+    /// a future setting the state with the transform future resolves with, spawn it on an
+    /// executor, the transform is ignored if the component unmounted before it resolved
+    #[inline]
+    pub fn set_state_async<T, F>(&self, future: T) -> SetStateAsync<T>
+    where
+        T: Future<Item = F>,
+        F: 'static + Send + Fn(&Props) -> Props,
+    {
+        SetStateAsync::new(self.clone(), future)
+    }
+
+    /// sets `loading` in the state to true and `error` to null, then returns a future that
+    /// sets `loading` to false when future resolves, applying its transform, or fails,
+    /// setting `error` to its error, see `set_state_async`
+    #[inline]
+    pub fn load<T, F>(&self, future: T) -> LoadState<T>
+    where
+        T: Future<Item = F>,
+        T::Error: Into<Prop>,
+        F: 'static + Send + Fn(&Props) -> Props,
+    {
+        self.set_state(|state| {
+            let mut next_state = state.clone();
+            next_state.set("loading", true);
+            next_state.set("error", Prop::Null);
+            next_state
+        });
+        LoadState::new(self.clone(), future)
+    }
+
     #[inline]
     pub fn force_update(&self) {
         self.set_state(Clone::clone);
@@ -0,0 +1,93 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use serde_json::{Map, Value};
+
+use super::super::{Prop, Props};
+use super::Updater;
+
+/// the function set as the `ref` prop, it only holds the id so the ref can be sent across
+/// threads
+type RefFunction = dyn Fn(&mut Props) -> Prop + Send + Sync;
+
+/// a handle to the host view rendered with the `ref` prop set to it, the id is set after
+/// the view is mounted and cleared after it is unmounted
+#[derive(Clone)]
+pub struct Ref {
+    id: Arc<Mutex<Option<String>>>,
+    func: Arc<RefFunction>,
+    updater: Updater,
+}
+
+impl Ref {
+    #[inline]
+    pub fn new(updater: &Updater) -> Self {
+        let id = Arc::new(Mutex::new(None));
+        let func_id = id.clone();
+
+        Ref {
+            id: id,
+            func: Arc::new(move |e: &mut Props| {
+                *func_id.lock().expect("failed to acquire Ref lock") =
+                    e.get("id").string().map(Clone::clone);
+                Prop::Null
+            }),
+            updater: updater.clone(),
+        }
+    }
+
+    /// the id of the view, None if it is not mounted
+    #[inline]
+    pub fn id(&self) -> Option<String> {
+        self.id.lock().expect("failed to acquire Ref lock").clone()
+    }
+    #[inline]
+    pub fn is_mounted(&self) -> bool {
+        self.id().is_some()
+    }
+
+    /// sends a `virtual_view.ref_call` message asking the host to call method on the view
+    /// with args, f is called with the host's response, returns false if the view is not
+    /// mounted
+    #[inline]
+    pub fn call<M, A, F>(&self, method: M, args: A, f: F) -> bool
+    where
+        M: Into<String>,
+        A: Into<Value>,
+        F: 'static + Fn(Value),
+    {
+        match self.id() {
+            Some(id) => {
+                let mut json = Map::new();
+
+                json.insert("id".into(), id.into());
+                json.insert("method".into(), method.into().into());
+                json.insert("args".into(), args.into());
+
+                self.updater.send("virtual_view.ref_call", json, f);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl From<Ref> for Prop {
+    #[inline]
+    fn from(view_ref: Ref) -> Self {
+        Prop::Function(view_ref.func)
+    }
+}
+impl<'a> From<&'a Ref> for Prop {
+    #[inline]
+    fn from(view_ref: &'a Ref) -> Self {
+        Prop::Function(view_ref.func.clone())
+    }
+}
+
+impl fmt::Debug for Ref {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Ref({:?})", self.id())
+    }
+}
@@ -0,0 +1,178 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use super::{Scheduler, Task};
+
+struct Timer {
+    id: usize,
+    at: Duration,
+    interval: Option<Duration>,
+    task: Option<Task>,
+}
+
+struct ManualClockInner {
+    id: usize,
+    now: Duration,
+    timers: Vec<Timer>,
+    frames: Vec<(usize, Task)>,
+}
+
+/// a scheduler for tests, time only passes when `advance` is called and frames are only
+/// drawn when `frame` is called
+#[derive(Clone)]
+pub struct ManualClock(Arc<Mutex<ManualClockInner>>);
+
+impl ManualClock {
+    #[inline]
+    pub fn new() -> Self {
+        ManualClock(Arc::new(Mutex::new(ManualClockInner {
+            id: 0,
+            now: Duration::from_millis(0),
+            timers: Vec::new(),
+            frames: Vec::new(),
+        })))
+    }
+
+    #[inline]
+    fn lock(&self) -> MutexGuard<ManualClockInner> {
+        self.0.lock().expect("failed to acquire clock lock")
+    }
+
+    /// the time passed since the clock was created
+    #[inline]
+    pub fn now(&self) -> Duration {
+        self.lock().now
+    }
+
+    /// the number of timers and frames waiting to be called
+    #[inline]
+    pub fn pending(&self) -> usize {
+        let inner = self.lock();
+        inner.timers.len() + inner.frames.len()
+    }
+
+    /// the time the next timer is due at, None if there is none
+    #[inline]
+    pub fn next_due_at(&self) -> Option<Duration> {
+        self.lock()
+            .timers
+            .iter()
+            .filter(|timer| timer.task.is_some())
+            .map(|timer| timer.at)
+            .min()
+    }
+
+    /// moves time forward by duration, calling the timers that are due in the order they
+    /// are due
+    #[inline]
+    pub fn advance(&self, duration: Duration) {
+        let target = self.now() + duration;
+
+        while let Some((id, mut task)) = self.next_due(target) {
+            task();
+            self.finish(id, task);
+        }
+
+        self.lock().now = target;
+    }
+
+    /// calls the frames requested before this call
+    #[inline]
+    pub fn frame(&self) {
+        let frames: Vec<(usize, Task)> = self.lock().frames.drain(..).collect();
+
+        for (_, mut task) in frames {
+            task();
+        }
+    }
+
+    /// takes the task of the earliest timer due at or before target and moves time to it
+    #[inline]
+    fn next_due(&self, target: Duration) -> Option<(usize, Task)> {
+        let mut inner = self.lock();
+
+        let (index, at) = match inner
+            .timers
+            .iter()
+            .enumerate()
+            .filter(|&(_, timer)| timer.task.is_some() && timer.at <= target)
+            .min_by_key(|&(_, timer)| (timer.at, timer.id))
+        {
+            Some((index, timer)) => (index, timer.at),
+            None => return None,
+        };
+
+        inner.now = at;
+        let timer = &mut inner.timers[index];
+        timer.task.take().map(|task| (timer.id, task))
+    }
+
+    /// reschedules an interval after its task ran, timeouts and cleared timers are removed
+    #[inline]
+    fn finish(&self, id: usize, task: Task) {
+        let mut inner = self.lock();
+
+        let index = match inner.timers.iter().position(|timer| timer.id == id) {
+            Some(index) => index,
+            None => return,
+        };
+
+        match inner.timers[index].interval {
+            Some(interval) => {
+                let timer = &mut inner.timers[index];
+                timer.at += interval;
+                timer.task = Some(task);
+            }
+            None => {
+                inner.timers.remove(index);
+            }
+        }
+    }
+
+    #[inline]
+    fn push(&self, delay: Duration, interval: Option<Duration>, task: Task) -> usize {
+        let mut inner = self.lock();
+        let id = inner.id;
+        let at = inner.now + delay;
+
+        inner.id += 1;
+        inner.timers.push(Timer {
+            id: id,
+            at: at,
+            interval: interval,
+            task: Some(task),
+        });
+
+        id
+    }
+}
+
+impl Scheduler for ManualClock {
+    #[inline]
+    fn set_timeout(&self, delay: Duration, task: Task) -> usize {
+        self.push(delay, None, task)
+    }
+    #[inline]
+    fn set_interval(&self, interval: Duration, task: Task) -> usize {
+        // a zero interval would be due forever
+        let interval = interval.max(Duration::new(0, 1));
+        self.push(interval, Some(interval), task)
+    }
+    #[inline]
+    fn request_frame(&self, task: Task) -> usize {
+        let mut inner = self.lock();
+        let id = inner.id;
+
+        inner.id += 1;
+        inner.frames.push((id, task));
+
+        id
+    }
+    #[inline]
+    fn clear(&self, id: usize) {
+        let mut inner = self.lock();
+
+        inner.timers.retain(|timer| timer.id != id);
+        inner.frames.retain(|&(frame_id, _)| frame_id != id);
+    }
+}
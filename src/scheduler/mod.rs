@@ -0,0 +1,7 @@
+mod manual_clock;
+mod scheduler;
+mod thread_scheduler;
+
+pub use self::manual_clock::ManualClock;
+pub use self::scheduler::{Scheduler, Task};
+pub use self::thread_scheduler::ThreadScheduler;
@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+/// a callback a scheduler calls once or, for intervals, until it is cleared
+pub type Task = Box<dyn FnMut() + Send>;
+
+/// schedules the timers and frames a renderer's components request
+pub trait Scheduler: Send + Sync {
+    /// calls task once after delay, returns an id to clear it with
+    fn set_timeout(&self, delay: Duration, task: Task) -> usize;
+
+    /// calls task every interval until it is cleared, returns an id to clear it with
+    fn set_interval(&self, interval: Duration, task: Task) -> usize;
+
+    /// calls task once before the host draws its next frame, returns an id to clear it with
+    fn request_frame(&self, task: Task) -> usize;
+
+    /// stops the timer or frame with id from being called, does nothing if it already ran
+    fn clear(&self, id: usize);
+}
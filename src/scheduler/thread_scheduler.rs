@@ -0,0 +1,174 @@
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use fnv::FnvHashMap;
+
+use super::{Scheduler, Task};
+
+struct Timer {
+    due: Instant,
+    interval: Option<Duration>,
+    /// None while the task runs
+    task: Option<Task>,
+}
+
+struct State {
+    id: usize,
+    timers: FnvHashMap<usize, Timer>,
+    running: bool,
+    stopped: bool,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    condvar: Condvar,
+}
+
+impl Shared {
+    #[inline]
+    fn lock(&self) -> MutexGuard<State> {
+        self.state.lock().expect("failed to acquire timers lock")
+    }
+
+    /// calls the tasks as they are due until the scheduler is dropped
+    #[inline]
+    fn run(&self) {
+        let mut state = self.lock();
+
+        while !state.stopped {
+            let next = state
+                .timers
+                .iter()
+                .filter(|&(_, timer)| timer.task.is_some())
+                .min_by_key(|&(id, timer)| (timer.due, *id))
+                .map(|(id, timer)| (*id, timer.due));
+
+            let (id, due) = match next {
+                Some(next) => next,
+                None => {
+                    state = self.condvar.wait(state).expect("failed to wait for timers");
+                    continue;
+                }
+            };
+
+            let now = Instant::now();
+
+            if now < due {
+                state = self
+                    .condvar
+                    .wait_timeout(state, due - now)
+                    .expect("failed to wait for timers")
+                    .0;
+                continue;
+            }
+
+            let mut task = match state
+                .timers
+                .get_mut(&id)
+                .and_then(|timer| timer.task.take())
+            {
+                Some(task) => task,
+                None => continue,
+            };
+            drop(state);
+
+            task();
+
+            state = self.lock();
+
+            // the timer may have been cleared while its task ran
+            let finished = match state.timers.get_mut(&id) {
+                Some(timer) => match timer.interval {
+                    Some(interval) => {
+                        timer.due += interval;
+                        timer.task = Some(task);
+                        false
+                    }
+                    None => true,
+                },
+                None => false,
+            };
+
+            if finished {
+                state.timers.remove(&id);
+            }
+        }
+    }
+}
+
+/// a scheduler calling every timer from one shared thread, started with the first timer,
+/// frames are requested every 16 milliseconds, the thread stops when the scheduler is
+/// dropped
+pub struct ThreadScheduler(Arc<Shared>);
+
+impl ThreadScheduler {
+    #[inline]
+    pub fn new() -> Self {
+        ThreadScheduler(Arc::new(Shared {
+            state: Mutex::new(State {
+                id: 0,
+                timers: FnvHashMap::default(),
+                running: false,
+                stopped: false,
+            }),
+            condvar: Condvar::new(),
+        }))
+    }
+
+    #[inline]
+    fn push(&self, delay: Duration, interval: Option<Duration>, task: Task) -> usize {
+        let mut state = self.0.lock();
+        let id = state.id;
+
+        state.id += 1;
+        state.timers.insert(
+            id,
+            Timer {
+                due: Instant::now() + delay,
+                interval: interval,
+                task: Some(task),
+            },
+        );
+
+        if !state.running {
+            let shared = self.0.clone();
+
+            state.running = true;
+            thread::spawn(move || shared.run());
+        }
+        self.0.condvar.notify_one();
+
+        id
+    }
+}
+
+impl Drop for ThreadScheduler {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.lock().stopped = true;
+        self.0.condvar.notify_one();
+    }
+}
+
+impl Scheduler for ThreadScheduler {
+    #[inline]
+    fn set_timeout(&self, delay: Duration, task: Task) -> usize {
+        self.push(delay, None, task)
+    }
+    #[inline]
+    fn set_interval(&self, interval: Duration, task: Task) -> usize {
+        // a zero interval would be due forever
+        let interval = interval.max(Duration::new(0, 1));
+        self.push(interval, Some(interval), task)
+    }
+    #[inline]
+    fn request_frame(&self, task: Task) -> usize {
+        self.push(Duration::from_millis(16), None, task)
+    }
+    #[inline]
+    fn clear(&self, id: usize) {
+        self.0.lock().timers.remove(&id);
+        self.0.condvar.notify_one();
+    }
+}
@@ -0,0 +1,111 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use futures::{Async, Poll, Stream};
+use messenger::{Message, MessageKind, Receiver, Sender};
+use serde_json::{from_value, Value};
+
+use super::super::{RawHost, Transaction};
+
+/// the transactions a renderer sent and the host they were applied to
+pub struct Capture {
+    pub transactions: Vec<Transaction>,
+    pub host: RawHost,
+    /// every other message the renderer sent that was not answered yet, by message id
+    pub messages: Vec<(u32, String, Value)>,
+}
+
+#[derive(Clone)]
+pub struct CaptureSender(Arc<Mutex<Capture>>);
+
+impl CaptureSender {
+    #[inline]
+    pub fn new() -> Self {
+        CaptureSender(Arc::new(Mutex::new(Capture {
+            transactions: Vec::new(),
+            host: RawHost::new(),
+            messages: Vec::new(),
+        })))
+    }
+
+    #[inline]
+    pub fn lock(&self) -> MutexGuard<Capture> {
+        self.0.lock().expect("failed to acquire capture lock")
+    }
+}
+
+impl Sender for CaptureSender {
+    type Item = Message<Value>;
+
+    /// applies transactions as they are sent, every other message is kept until it is
+    /// answered, the answers of the renderer to host messages are dropped
+    #[inline]
+    fn send(&self, message: Self::Item) -> Result<(), Self::Item> {
+        let message_id = message.id();
+
+        if let MessageKind::Data(name, value) = message.take_kind() {
+            if name == "virtual_view.transaction" {
+                let transaction: Transaction =
+                    from_value(value).expect("failed to deserialize transaction");
+
+                let result = {
+                    let mut capture = self.lock();
+                    let result = capture.host.apply(&transaction);
+                    capture.transactions.push(transaction);
+                    result
+                };
+
+                if let Err(error) = result {
+                    panic!("failed to apply transaction: {}", error);
+                }
+            } else {
+                self.lock().messages.push((message_id, name, value));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// the messages the host sends the renderer, they are received when the test renderer polls
+/// its messenger
+#[derive(Clone)]
+pub struct CaptureReceiver(Arc<Mutex<VecDeque<Message<Value>>>>);
+
+impl CaptureReceiver {
+    #[inline]
+    pub fn new() -> Self {
+        CaptureReceiver(Arc::new(Mutex::new(VecDeque::new())))
+    }
+
+    #[inline]
+    pub fn push(&self, message: Message<Value>) {
+        self.0
+            .lock()
+            .expect("failed to acquire capture receiver lock")
+            .push_back(message);
+    }
+}
+
+impl Stream for CaptureReceiver {
+    type Item = Message<Value>;
+    type Error = ();
+
+    #[inline]
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        let message = self
+            .0
+            .lock()
+            .expect("failed to acquire capture receiver lock")
+            .pop_front();
+
+        match message {
+            Some(message) => Ok(Async::Ready(Some(message))),
+            None => Ok(Async::NotReady),
+        }
+    }
+}
+
+impl Receiver for CaptureReceiver {
+    #[inline]
+    fn close(&mut self) {}
+}
@@ -0,0 +1,4 @@
+mod capture;
+mod test_renderer;
+
+pub use self::test_renderer::TestRenderer;
@@ -0,0 +1,249 @@
+use std::cell::RefCell;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::executor::{self, Notify, Spawn};
+use futures::Future;
+use messenger::{Message, Messenger};
+use serde_json::{Map, Value};
+
+use super::super::{
+    view_id, EventManager, Listener, ManualClock, Props, RawView, Renderer, Transaction, View,
+};
+use super::capture::{CaptureReceiver, CaptureSender};
+
+type MessengerFuture = Spawn<Box<dyn Future<Item = (), Error = ()>>>;
+
+/// a renderer for tests, transactions are recorded and applied to a `RawHost` as they are
+/// sent so the rendered tree can be inspected right after mounting or dispatching events,
+/// timers and frames only run when `advance` or `frame` is called, the other messages it
+/// sends wait in `messages` until they are answered with `respond`
+pub struct TestRenderer {
+    renderer: Renderer,
+    sender: CaptureSender,
+    receiver: CaptureReceiver,
+    messenger: RefCell<MessengerFuture>,
+    clock: ManualClock,
+}
+
+impl TestRenderer {
+    #[inline]
+    pub fn new(view: View) -> Self {
+        let sender = CaptureSender::new();
+        let receiver = CaptureReceiver::new();
+        let (messenger, future) = Messenger::<Value>::new(sender.clone(), receiver.clone());
+        let clock = ManualClock::new();
+        let renderer = Renderer::with_scheduler(EventManager::new(), messenger, clock.clone());
+        let future: Box<dyn Future<Item = (), Error = ()>> = Box::new(future);
+
+        renderer.mount(view);
+
+        TestRenderer {
+            renderer: renderer,
+            sender: sender,
+            receiver: receiver,
+            messenger: RefCell::new(executor::spawn(future)),
+            clock: clock,
+        }
+    }
+
+    #[inline]
+    pub fn renderer(&self) -> &Renderer {
+        &self.renderer
+    }
+    #[inline]
+    pub fn root_id(&self) -> &String {
+        self.renderer.root_id()
+    }
+    #[inline]
+    pub fn clock(&self) -> &ManualClock {
+        &self.clock
+    }
+
+    /// moves the clock forward by duration, running the timers on this thread as they are
+    /// due, see `ManualClock::advance`
+    #[inline]
+    pub fn advance(&self, duration: Duration) {
+        let target = self.clock.now() + duration;
+
+        while let Some(at) = self.clock.next_due_at() {
+            if at > target {
+                break;
+            }
+            self.clock.advance(at - self.clock.now());
+            self.renderer.process();
+        }
+
+        self.clock.advance(target - self.clock.now());
+        self.renderer.process();
+    }
+    /// draws a frame, running the frames requested before this call
+    #[inline]
+    pub fn frame(&self) {
+        self.clock.frame();
+        self.renderer.process();
+    }
+
+    /// every transaction sent so far
+    #[inline]
+    pub fn transactions(&self) -> Vec<Transaction> {
+        self.sender.lock().transactions.clone()
+    }
+    /// the transactions sent since the last call
+    #[inline]
+    pub fn take_transactions(&self) -> Vec<Transaction> {
+        self.sender.lock().transactions.drain(..).collect()
+    }
+
+    /// the names and data of the messages sent to the host that were not answered yet,
+    /// like ref calls and requests, transactions are not included
+    #[inline]
+    pub fn messages(&self) -> Vec<(String, Value)> {
+        self.sender
+            .lock()
+            .messages
+            .iter()
+            .map(|&(_, ref name, ref data)| (name.clone(), data.clone()))
+            .collect()
+    }
+    /// answers the oldest message named name with data, calling the callback it was sent
+    /// with, returns false if there is no such message
+    #[inline]
+    pub fn respond(&self, name: &str, data: Value) -> bool {
+        let message_id = {
+            let mut capture = self.sender.lock();
+
+            match capture.messages.iter().position(|m| m.1 == name) {
+                Some(index) => capture.messages.remove(index).0,
+                None => return false,
+            }
+        };
+
+        self.receive(Message::new_callback(message_id, vec![data]));
+        true
+    }
+    /// sends the renderer a `virtual_view.message` as the host would, calling the handlers
+    /// components subscribed to name with data
+    #[inline]
+    pub fn message(&self, name: &str, data: Value) {
+        let mut json = Map::new();
+
+        json.insert("name".into(), name.into());
+        json.insert("data".into(), data);
+
+        self.receive(Message::new(
+            0,
+            "virtual_view.message".into(),
+            Value::Object(json),
+        ));
+    }
+
+    #[inline]
+    fn receive(&self, message: Message<Value>) {
+        self.receiver.push(message);
+
+        let _ = self
+            .messenger
+            .borrow_mut()
+            .poll_future_notify(&Arc::new(NoopNotify), 0);
+    }
+
+    /// the tree the host shows at the root
+    #[inline]
+    pub fn tree(&self) -> Option<RawView> {
+        self.view(self.renderer.root_id())
+    }
+    /// the view the host shows at id
+    #[inline]
+    pub fn view(&self, id: &str) -> Option<RawView> {
+        self.sender.lock().host.view(id)
+    }
+    /// the views the host shows in the container, like the target of a portal
+    #[inline]
+    pub fn container(&self, id: &str) -> Option<Vec<RawView>> {
+        self.sender.lock().host.container(id)
+    }
+    #[inline]
+    pub fn has_event(&self, id: &str, name: &str) -> bool {
+        self.sender.lock().host.has_event(id, name)
+    }
+    #[inline]
+    pub fn listener(&self, id: &str, name: &str) -> Option<Listener> {
+        self.sender.lock().host.listener(id, name).map(Clone::clone)
+    }
+
+    /// the ids and views in the tree with the kind, in document order
+    #[inline]
+    pub fn find_by_kind(&self, kind: &str) -> Vec<(String, RawView)> {
+        self.find(|view| view.kind().map(|k| k == kind).unwrap_or(false))
+    }
+    /// the ids and views in the tree with the prop set to value, in document order
+    #[inline]
+    pub fn find_by_prop<V>(&self, name: &str, value: V) -> Vec<(String, RawView)>
+    where
+        V: Into<Value>,
+    {
+        let value = value.into();
+        self.find(|view| {
+            view.props()
+                .and_then(|props| props.get(name))
+                .map(|v| v == &value)
+                .unwrap_or(false)
+        })
+    }
+    #[inline]
+    pub fn find<F>(&self, f: F) -> Vec<(String, RawView)>
+    where
+        F: Fn(&RawView) -> bool,
+    {
+        let mut found = Vec::new();
+
+        if let Some(tree) = self.tree() {
+            find(self.renderer.root_id(), &tree, &f, &mut found);
+        }
+
+        found
+    }
+
+    /// dispatches the event named name at id through the event manager, returning the event
+    /// after the handlers ran
+    #[inline]
+    pub fn simulate(&self, id: &str, name: &str, mut event: Props) -> Props {
+        event.set("name", name);
+        self.renderer.dispatch(id, &mut event);
+        event
+    }
+
+    #[inline]
+    pub fn render(&self, view: View) {
+        self.renderer.render(view);
+    }
+    #[inline]
+    pub fn unmount(&self) {
+        self.renderer.unmount();
+    }
+}
+
+/// the messenger is polled right after a message is received, it never has to be woken
+struct NoopNotify;
+
+impl Notify for NoopNotify {
+    #[inline]
+    fn notify(&self, _: usize) {}
+}
+
+#[inline]
+fn find<F>(id: &str, view: &RawView, f: &F, found: &mut Vec<(String, RawView)>)
+where
+    F: Fn(&RawView) -> bool,
+{
+    if f(view) {
+        found.push((id.into(), view.clone()));
+    }
+
+    if let Some(children) = view.children() {
+        for (index, child) in children.iter().enumerate() {
+            find(&view_id(id, child.key(), index), child, f, found);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+/// a difference found between a server rendered view and the client view while hydrating
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Mismatch {
+    Kind(String, Option<String>, Option<String>),
+    Key(String, Option<String>, Option<String>),
+    Text(String, String, String),
+    Props(String),
+    Children(String, usize, usize),
+}
+
+impl Mismatch {
+    #[inline]
+    pub fn id(&self) -> &String {
+        match self {
+            &Mismatch::Kind(ref id, _, _) => id,
+            &Mismatch::Key(ref id, _, _) => id,
+            &Mismatch::Text(ref id, _, _) => id,
+            &Mismatch::Props(ref id) => id,
+            &Mismatch::Children(ref id, _, _) => id,
+        }
+    }
+
+    #[inline]
+    pub fn is_kind(&self) -> bool {
+        match self {
+            &Mismatch::Kind(_, _, _) => true,
+            _ => false,
+        }
+    }
+
+    #[inline]
+    pub fn is_key(&self) -> bool {
+        match self {
+            &Mismatch::Key(_, _, _) => true,
+            _ => false,
+        }
+    }
+
+    #[inline]
+    pub fn is_text(&self) -> bool {
+        match self {
+            &Mismatch::Text(_, _, _) => true,
+            _ => false,
+        }
+    }
+
+    #[inline]
+    pub fn is_props(&self) -> bool {
+        match self {
+            &Mismatch::Props(_) => true,
+            _ => false,
+        }
+    }
+
+    #[inline]
+    pub fn is_children(&self) -> bool {
+        match self {
+            &Mismatch::Children(_, _, _) => true,
+            _ => false,
+        }
+    }
+}
@@ -1,8 +1,10 @@
+mod mismatch;
 mod order;
 mod patch;
 mod raw_view;
 mod transaction;
 
+pub use self::mismatch::Mismatch;
 pub use self::order::Order;
 pub use self::patch::Patch;
 pub use self::raw_view::RawView;
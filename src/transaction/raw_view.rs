@@ -11,6 +11,9 @@ pub enum RawView {
         props: Map<String, Value>,
         children: Vec<RawView>,
     },
+    /// the views a component or the root rendered as a fragment, hosts lay its children out
+    /// as children of the fragment's parent, but address them below the fragment's id
+    Fragment(Vec<RawView>),
 }
 
 unsafe impl Sync for RawView {}
@@ -33,6 +36,9 @@ impl<'a> From<&'a View> for RawView {
                 props: props_to_json(props),
                 children: children.iter().map(|child| RawView::from(child)).collect(),
             },
+            &View::Fragment(ref children) => {
+                RawView::Fragment(children.iter().map(|child| RawView::from(child)).collect())
+            }
         }
     }
 }
@@ -57,6 +63,12 @@ impl From<View> for RawView {
                     .map(|child| RawView::from(child))
                     .collect(),
             },
+            View::Fragment(children) => RawView::Fragment(
+                children
+                    .into_iter()
+                    .map(|child| RawView::from(child))
+                    .collect(),
+            ),
         }
     }
 }
@@ -65,22 +77,22 @@ impl RawView {
     #[inline]
     pub fn kind(&self) -> Option<&String> {
         match self {
-            &RawView::Text(_) => None,
             &RawView::Data { ref kind, .. } => Some(kind),
+            _ => None,
         }
     }
     #[inline]
     pub fn key(&self) -> Option<&String> {
         match self {
-            &RawView::Text(_) => None,
             &RawView::Data { ref key, .. } => key.as_ref(),
+            _ => None,
         }
     }
     #[inline]
     pub fn props(&self) -> Option<&Map<String, Value>> {
         match self {
-            &RawView::Text(_) => None,
             &RawView::Data { ref props, .. } => Some(props),
+            _ => None,
         }
     }
     #[inline]
@@ -88,6 +100,7 @@ impl RawView {
         match self {
             &RawView::Text(_) => None,
             &RawView::Data { ref children, .. } => Some(children),
+            &RawView::Fragment(ref children) => Some(children),
         }
     }
 }
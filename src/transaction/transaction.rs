@@ -1,11 +1,17 @@
 use serde_json::{Map, Value};
 use fnv::FnvHashMap;
 
+use super::super::{is_ancestor_id_of, Listener};
 use super::{Order, Patch, RawView};
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Transaction {
+    /// view ids to prop names, true to listen and false to stop listening
     events: FnvHashMap<String, FnvHashMap<String, bool>>,
+    /// view ids to prop names to how to listen for the events added in `events`, hosts that do
+    /// not support listener options can ignore it
+    #[serde(default, skip_serializing_if = "FnvHashMap::is_empty")]
+    listeners: FnvHashMap<String, FnvHashMap<String, Listener>>,
     removes: FnvHashMap<String, RawView>,
     patches: FnvHashMap<String, Vec<Patch>>,
 }
@@ -15,6 +21,7 @@ impl Transaction {
     pub fn new() -> Self {
         Transaction {
             events: FnvHashMap::default(),
+            listeners: FnvHashMap::default(),
             removes: FnvHashMap::default(),
             patches: FnvHashMap::default(),
         }
@@ -34,8 +41,11 @@ impl Transaction {
         self.removes.insert(id.into(), view);
     }
 
+    /// inserts view at index of id, hosts move a view that already has view_id, so a remove
+    /// of view_id queued before is dropped
     #[inline]
     pub fn insert(&mut self, id: &str, view_id: &str, index: usize, view: RawView) {
+        self.removes.remove(view_id);
         self.append(id.into(), Patch::Insert(view_id.into(), index, view));
     }
     #[inline]
@@ -65,9 +75,45 @@ impl Transaction {
     pub fn add_event(&mut self, id: &str, name: &str) {
         self.append_event(id.into(), name.into(), true);
     }
+    /// adds the event with the options of listener
+    #[inline]
+    pub fn add_listener(&mut self, id: &str, name: &str, listener: Listener) {
+        self.add_event(id, name);
+        self.listeners
+            .entry(id.into())
+            .or_insert_with(FnvHashMap::default)
+            .insert(name.into(), listener);
+    }
     #[inline]
     pub fn remove_event(&mut self, id: &str, name: &str) {
         self.append_event(id.into(), name.into(), false);
+
+        let remove = if let Some(listeners) = self.listeners.get_mut(id) {
+            listeners.remove(name);
+            listeners.is_empty()
+        } else {
+            false
+        };
+
+        if remove {
+            self.listeners.remove(id);
+        }
+    }
+
+    /// drops every event, remove and patch at or below id, including the inserts of views
+    /// below id into other containers
+    #[inline]
+    pub fn clear_subtree(&mut self, id: &str) {
+        self.events.retain(|k, _| !is_ancestor_id_of(id, k));
+        self.listeners.retain(|k, _| !is_ancestor_id_of(id, k));
+        self.removes.retain(|k, _| !is_ancestor_id_of(id, k));
+        self.patches.retain(|k, patches| {
+            patches.retain(|patch| match patch {
+                &Patch::Insert(ref view_id, _, _) => !is_ancestor_id_of(id, view_id),
+                _ => true,
+            });
+            !is_ancestor_id_of(id, k) && !patches.is_empty()
+        });
     }
 
     #[inline(always)]
@@ -75,6 +121,20 @@ impl Transaction {
         &self.events
     }
     #[inline(always)]
+    pub fn listeners(&self) -> &FnvHashMap<String, FnvHashMap<String, Listener>> {
+        &self.listeners
+    }
+    /// the options to listen for the event added at id, events without listener options are
+    /// listened to with the defaults of their prop name
+    #[inline]
+    pub fn listener(&self, id: &str, name: &str) -> Listener {
+        self.listeners
+            .get(id)
+            .and_then(|listeners| listeners.get(name))
+            .map(Clone::clone)
+            .unwrap_or_else(|| Listener::from_key(name))
+    }
+    #[inline(always)]
     pub fn removes(&self) -> &FnvHashMap<String, RawView> {
         &self.removes
     }
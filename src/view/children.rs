@@ -2,3 +2,27 @@ use super::View;
 
 
 pub type Children = Vec<View>;
+
+/// replaces every fragment in children with the fragment's own children
+#[inline]
+pub fn flatten_children(children: &mut Children) {
+    if children.iter().any(View::is_fragment) {
+        let mut flat_children = Children::with_capacity(children.len());
+
+        for child in children.drain(..) {
+            push_flat(&mut flat_children, child);
+        }
+
+        *children = flat_children;
+    }
+}
+
+#[inline]
+fn push_flat(flat_children: &mut Children, view: View) {
+    match view {
+        View::Fragment(children) => for child in children {
+            push_flat(flat_children, child);
+        },
+        view => flat_children.push(view),
+    }
+}
@@ -1,7 +1,7 @@
 use std::any::Any;
 
 use super::super::{Instance, Props};
-use super::{Children, View};
+use super::{Children, RenderError, TypedContext, View};
 
 pub trait Component: 'static + Any {
     fn render(&self, instance: &Instance, props: &Props, children: &Children) -> View;
@@ -22,12 +22,33 @@ pub trait Component: 'static + Any {
     fn context(&self, _props: &Props) -> Props {
         Props::new()
     }
-    /// filter the passed context of the component
+    /// filter the passed context of the component, defaults to the component's context only,
+    /// unlike `inherit_typed_context` the parent's context is not passed on
     #[inline]
     fn inherit_context(&self, context: Props, _parent_context: &Props) -> Props {
         context
     }
 
+    /// return the inital typed context of the component, gets passed to children via
+    /// inherit_typed_context
+    #[inline]
+    fn typed_context(&self, _props: &Props) -> TypedContext {
+        TypedContext::new()
+    }
+    /// filter the passed typed context of the component, defaults to the parent's typed
+    /// context with the component's values added, unlike `inherit_context` which drops the
+    /// parent's context
+    #[inline]
+    fn inherit_typed_context(
+        &self,
+        context: TypedContext,
+        parent_context: &TypedContext,
+    ) -> TypedContext {
+        let mut typed_context = parent_context.clone();
+        typed_context.extend(&context);
+        typed_context
+    }
+
     /// called before mount
     #[inline(always)]
     fn will_mount(&self, _instance: &Instance) {}
@@ -40,6 +61,18 @@ pub trait Component: 'static + Any {
     #[inline(always)]
     fn will_update(&self, _instance: &Instance) {}
 
+    /// called after the mount transaction is sent, children before parents
+    #[inline(always)]
+    fn did_mount(&self, _instance: &Instance) {}
+
+    /// called after the unmount transaction is sent, children before parents
+    #[inline(always)]
+    fn did_unmount(&self, _instance: &Instance) {}
+
+    /// called after the update transaction is sent, children before parents
+    #[inline(always)]
+    fn did_update(&self, _instance: &Instance, _prev_props: &Props, _prev_state: &Props) {}
+
     /// called when component receives new state, props, or children
     #[inline(always)]
     fn receive_props(
@@ -51,6 +84,13 @@ pub trait Component: 'static + Any {
     ) {
     }
 
+    /// called when rendering a descendant panics, return a view to render in place of the
+    /// children, or None to pass the error to the next ancestor
+    #[inline]
+    fn catch_error(&self, _instance: &Instance, _error: &RenderError) -> Option<View> {
+        None
+    }
+
     /// if component needs update return true, defaults to true
     #[inline]
     fn should_update(
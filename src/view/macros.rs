@@ -103,6 +103,29 @@ pub fn child_to_parent(stack: &mut Vec<View>, end_tag: Option<&'static str>) {
     }
 }
 
+#[doc(hidden)]
+#[inline]
+pub fn child_to_parent_fragment(stack: &mut Vec<View>) {
+    if let Some(view) = stack.pop() {
+        if !view.is_fragment() {
+            panic!("wrong closing fragment: <{:?}> -> </>", view.kind());
+        }
+
+        if !stack.is_empty() {
+            stack
+                .last_mut()
+                .unwrap()
+                .children_mut()
+                .expect("text view can not have children")
+                .push(view);
+        } else {
+            stack.push(view);
+        }
+    } else {
+        panic!("redundant closing fragment");
+    }
+}
+
 #[doc(hidden)]
 #[inline]
 pub fn child_to_parent_component(stack: &mut Vec<View>, end_type_id: TypeId) {
@@ -136,6 +159,17 @@ pub fn child_to_parent_component(stack: &mut Vec<View>, end_type_id: TypeId) {
 #[doc(hidden)]
 #[macro_export]
 macro_rules! view_internal {
+    // Start of opening fragment
+    ($stack:ident (< > $($tail:tt)*)) => (
+        $stack.push($crate::View::new_fragment());
+        view_internal! { $stack ($($tail)*) }
+    );
+    // Fragment closing
+    ($stack:ident (< / > $($tail:tt)*)) => (
+        $crate::view::macros::child_to_parent_fragment(&mut $stack);
+        view_internal! { $stack ($($tail)*) }
+    );
+
     // Start of opening tag
     ($stack:ident (< $start_tag:ident $($tail:tt)*)) => (
         $stack.push($crate::View::new_data(stringify!($start_tag)));
@@ -2,10 +2,16 @@ mod children;
 mod component;
 #[macro_use]
 pub mod macros;
+mod pure_component;
+mod render_error;
+mod typed_context;
 mod view_kind;
 mod view;
 
-pub use self::children::Children;
+pub use self::children::{flatten_children, Children};
 pub use self::component::Component;
+pub use self::pure_component::{memo, PureComponent};
+pub use self::render_error::RenderError;
+pub use self::typed_context::TypedContext;
 pub use self::view_kind::ViewKind;
 pub use self::view::View;
@@ -0,0 +1,168 @@
+use super::super::{Instance, Props};
+use super::{Children, Component, RenderError, TypedContext, View};
+
+/// wraps a component so it only updates when its state, props or children change
+pub struct PureComponent<C>
+where
+    C: Component,
+{
+    component: C,
+    props_equal: Option<Box<dyn Fn(&Props, &Props) -> bool>>,
+}
+
+impl<C> PureComponent<C>
+where
+    C: Component,
+{
+    #[inline]
+    pub fn new(component: C) -> Self {
+        PureComponent {
+            component: component,
+            props_equal: None,
+        }
+    }
+
+    /// compares props with props_equal instead of `PartialEq`, it is called with the prev
+    /// and next props and returns true if they are equal
+    #[inline]
+    pub fn with_props_equal<F>(component: C, props_equal: F) -> Self
+    where
+        F: 'static + Fn(&Props, &Props) -> bool,
+    {
+        PureComponent {
+            component: component,
+            props_equal: Some(Box::new(props_equal)),
+        }
+    }
+
+    #[inline(always)]
+    pub fn component(&self) -> &C {
+        &self.component
+    }
+}
+
+/// wraps component in a `PureComponent`
+#[inline]
+pub fn memo<C>(component: C) -> PureComponent<C>
+where
+    C: Component,
+{
+    PureComponent::new(component)
+}
+
+impl<C> Component for PureComponent<C>
+where
+    C: Component,
+{
+    #[inline]
+    fn render(&self, instance: &Instance, props: &Props, children: &Children) -> View {
+        self.component.render(instance, props, children)
+    }
+
+    #[inline(always)]
+    fn name(&self) -> &'static str {
+        self.component.name()
+    }
+
+    #[inline]
+    fn initial_state(&self, props: &Props) -> Props {
+        self.component.initial_state(props)
+    }
+
+    #[inline]
+    fn context(&self, props: &Props) -> Props {
+        self.component.context(props)
+    }
+    #[inline]
+    fn inherit_context(&self, context: Props, parent_context: &Props) -> Props {
+        self.component.inherit_context(context, parent_context)
+    }
+
+    #[inline]
+    fn typed_context(&self, props: &Props) -> TypedContext {
+        self.component.typed_context(props)
+    }
+    #[inline]
+    fn inherit_typed_context(
+        &self,
+        context: TypedContext,
+        parent_context: &TypedContext,
+    ) -> TypedContext {
+        self.component
+            .inherit_typed_context(context, parent_context)
+    }
+
+    #[inline(always)]
+    fn will_mount(&self, instance: &Instance) {
+        self.component.will_mount(instance)
+    }
+    #[inline(always)]
+    fn will_unmount(&self, instance: &Instance) {
+        self.component.will_unmount(instance)
+    }
+    #[inline(always)]
+    fn will_update(&self, instance: &Instance) {
+        self.component.will_update(instance)
+    }
+    #[inline(always)]
+    fn did_mount(&self, instance: &Instance) {
+        self.component.did_mount(instance)
+    }
+    #[inline(always)]
+    fn did_unmount(&self, instance: &Instance) {
+        self.component.did_unmount(instance)
+    }
+    #[inline(always)]
+    fn did_update(&self, instance: &Instance, prev_props: &Props, prev_state: &Props) {
+        self.component.did_update(instance, prev_props, prev_state)
+    }
+
+    #[inline(always)]
+    fn receive_props(
+        &self,
+        instance: &Instance,
+        next_state: &Props,
+        next_props: &Props,
+        next_children: &Children,
+    ) {
+        self.component
+            .receive_props(instance, next_state, next_props, next_children)
+    }
+
+    #[inline]
+    fn catch_error(&self, instance: &Instance, error: &RenderError) -> Option<View> {
+        self.component.catch_error(instance, error)
+    }
+
+    /// true if the state, props or children changed and the wrapped component's
+    /// should_update returns true
+    #[inline]
+    fn should_update(
+        &self,
+        prev_state: &Props,
+        prev_props: &Props,
+        prev_children: &Children,
+
+        next_state: &Props,
+        next_props: &Props,
+        next_children: &Children,
+    ) -> bool {
+        let props_equal = match self.props_equal {
+            Some(ref props_equal) => props_equal(prev_props, next_props),
+            None => prev_props == next_props,
+        };
+
+        if props_equal && prev_state == next_state && prev_children == next_children {
+            false
+        } else {
+            self.component.should_update(
+                prev_state,
+                prev_props,
+                prev_children,
+                next_state,
+                next_props,
+                next_children,
+            )
+        }
+    }
+}
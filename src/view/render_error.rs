@@ -0,0 +1,76 @@
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
+
+/// the error passed to `Component::catch_error` when rendering a descendant panics
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderError {
+    id: String,
+    name: &'static str,
+    message: String,
+}
+
+impl RenderError {
+    #[inline]
+    pub fn new<I, M>(id: I, name: &'static str, message: M) -> Self
+    where
+        I: Into<String>,
+        M: Into<String>,
+    {
+        RenderError {
+            id: id.into(),
+            name: name,
+            message: message.into(),
+        }
+    }
+
+    /// creates an error from a panic payload caught while rendering
+    #[inline]
+    pub fn from_panic<I>(id: I, name: &'static str, payload: &Box<dyn Any + Send>) -> Self
+    where
+        I: Into<String>,
+    {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            (*message).to_owned()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "unknown error".to_owned()
+        };
+
+        Self::new(id, name, message)
+    }
+
+    /// the view id of the component that failed to render
+    #[inline(always)]
+    pub fn id(&self) -> &String {
+        &self.id
+    }
+    /// the name of the component that failed to render
+    #[inline(always)]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+    #[inline(always)]
+    pub fn message(&self) -> &String {
+        &self.message
+    }
+}
+
+impl fmt::Display for RenderError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at {} failed to render: {}",
+            self.name, self.id, self.message
+        )
+    }
+}
+
+impl Error for RenderError {
+    #[inline]
+    fn description(&self) -> &str {
+        &self.message
+    }
+}
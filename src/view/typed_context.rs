@@ -0,0 +1,136 @@
+use std::any::{Any, TypeId};
+use std::fmt;
+use std::sync::Arc;
+
+use fnv::FnvHashMap;
+
+type Value = Arc<dyn Any + Send + Sync>;
+type Eq = fn(&Value, &Value) -> bool;
+
+/// context values keyed by their type, used to pass services like stores or clients to
+/// the components below
+#[derive(Clone, Default)]
+pub struct TypedContext(FnvHashMap<TypeId, (Value, Option<Eq>)>);
+
+impl TypedContext {
+    #[inline]
+    pub fn new() -> Self {
+        TypedContext(FnvHashMap::default())
+    }
+
+    #[inline]
+    pub fn insert<T>(&mut self, value: T)
+    where
+        T: 'static + Send + Sync,
+    {
+        self.insert_arc(Arc::new(value));
+    }
+    #[inline]
+    pub fn insert_arc<T>(&mut self, value: Arc<T>)
+    where
+        T: 'static + Send + Sync,
+    {
+        self.0.insert(TypeId::of::<T>(), (value, None));
+    }
+    /// inserts a value compared by `PartialEq` instead of by pointer, so providers creating
+    /// the value on every render do not update the components below when it is equal
+    #[inline]
+    pub fn insert_eq<T>(&mut self, value: T)
+    where
+        T: 'static + Send + Sync + PartialEq,
+    {
+        self.insert_arc_eq(Arc::new(value));
+    }
+    #[inline]
+    pub fn insert_arc_eq<T>(&mut self, value: Arc<T>)
+    where
+        T: 'static + Send + Sync + PartialEq,
+    {
+        self.0
+            .insert(TypeId::of::<T>(), (value, Some(value_eq::<T>)));
+    }
+
+    #[inline]
+    pub fn get<T>(&self) -> Option<Arc<T>>
+    where
+        T: 'static + Send + Sync,
+    {
+        self.0
+            .get(&TypeId::of::<T>())
+            .and_then(|&(ref value, _)| value.clone().downcast::<T>().ok())
+    }
+    #[inline]
+    pub fn has<T>(&self) -> bool
+    where
+        T: 'static + Send + Sync,
+    {
+        self.0.contains_key(&TypeId::of::<T>())
+    }
+    #[inline]
+    pub fn remove<T>(&mut self) -> Option<Arc<T>>
+    where
+        T: 'static + Send + Sync,
+    {
+        self.0
+            .remove(&TypeId::of::<T>())
+            .and_then(|(value, _)| value.downcast::<T>().ok())
+    }
+
+    /// adds every value of other, replacing the values of the same types
+    #[inline]
+    pub fn extend(&mut self, other: &TypedContext) {
+        for (type_id, value) in &other.0 {
+            self.0.insert(*type_id, value.clone());
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl PartialEq for TypedContext {
+    /// contexts are equal if they have the same values, values inserted with `insert_eq`
+    /// are also equal if they compare equal
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len()
+            && self
+                .0
+                .iter()
+                .all(|(type_id, &(ref value, eq))| match other.0.get(type_id) {
+                    Some(&(ref other_value, _)) => {
+                        Arc::ptr_eq(value, other_value)
+                            || eq.map(|eq| eq(value, other_value)).unwrap_or(false)
+                    }
+                    None => false,
+                })
+    }
+}
+
+#[inline]
+fn value_eq<T>(a: &Value, b: &Value) -> bool
+where
+    T: 'static + PartialEq,
+{
+    match (a.downcast_ref::<T>(), b.downcast_ref::<T>()) {
+        (Some(a), Some(b)) => a == b,
+        _ => false,
+    }
+}
+
+impl fmt::Debug for TypedContext {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "TypedContext({:?})",
+            self.0.keys().collect::<Vec<&TypeId>>()
+        )
+    }
+}
@@ -12,6 +12,8 @@ pub enum View {
         props: Props,
         children: Children,
     },
+    /// sibling views without a wrapper, flattened into the children of the view containing it
+    Fragment(Children),
 }
 
 unsafe impl Sync for View {}
@@ -51,6 +53,24 @@ impl View {
         }
     }
     #[inline]
+    pub fn new_fragment() -> Self {
+        View::Fragment(Children::new())
+    }
+    /// a view rendering children into the host container with the target id, it keeps its
+    /// place in the parent's context and event bubbling
+    #[inline]
+    pub fn new_portal<T>(target: T, children: Children) -> Self
+    where
+        T: Into<String>,
+    {
+        View::Data {
+            kind: ViewKind::Portal(target.into()),
+            key: None,
+            props: Props::new(),
+            children: children,
+        }
+    }
+    #[inline]
     pub fn new_component<T>(component: T) -> Self
     where
         T: Component,
@@ -67,22 +87,29 @@ impl View {
     pub fn is_text(&self) -> bool {
         match self {
             &View::Text(_) => true,
-            &View::Data { .. } => false,
+            _ => false,
         }
     }
     #[inline]
     pub fn is_data(&self) -> bool {
         match self {
-            &View::Text(_) => false,
             &View::Data { .. } => true,
+            _ => false,
+        }
+    }
+    #[inline]
+    pub fn is_fragment(&self) -> bool {
+        match self {
+            &View::Fragment(_) => true,
+            _ => false,
         }
     }
 
     #[inline]
     pub fn kind(&self) -> Option<&ViewKind> {
         match self {
-            &View::Text(_) => None,
             &View::Data { ref kind, .. } => Some(kind),
+            _ => None,
         }
     }
     #[inline]
@@ -93,6 +120,13 @@ impl View {
         }
     }
     #[inline]
+    pub fn portal_target(&self) -> Option<&String> {
+        match self.kind() {
+            Some(&ViewKind::Portal(ref target)) => Some(target),
+            _ => None,
+        }
+    }
+    #[inline]
     pub fn component(&self) -> Option<&Arc<dyn Component>> {
         match self.kind() {
             Some(&ViewKind::Component(ref component)) => Some(component),
@@ -103,22 +137,22 @@ impl View {
     #[inline]
     pub fn has_key(&self) -> bool {
         match self {
-            &View::Text(_) => false,
             &View::Data { ref key, .. } => key.is_some(),
+            _ => false,
         }
     }
     #[inline]
     pub fn clone_key(&self) -> Option<String> {
         match self {
-            &View::Text(_) => None,
             &View::Data { ref key, .. } => key.clone(),
+            _ => None,
         }
     }
     #[inline]
     pub fn key(&self) -> Option<&String> {
         match self {
-            &View::Text(_) => None,
             &View::Data { ref key, .. } => key.as_ref(),
+            _ => None,
         }
     }
     #[inline]
@@ -128,23 +162,23 @@ impl View {
     #[inline]
     pub fn set_key_option(&mut self, new_key: Option<String>) {
         match self {
-            &mut View::Text(_) => (),
             &mut View::Data { ref mut key, .. } => *key = new_key,
+            _ => (),
         }
     }
 
     #[inline]
     pub fn props(&self) -> Option<&Props> {
         match self {
-            &View::Text(_) => None,
             &View::Data { ref props, .. } => Some(props),
+            _ => None,
         }
     }
     #[inline]
     pub fn props_mut(&mut self) -> Option<&mut Props> {
         match self {
-            &mut View::Text(_) => None,
             &mut View::Data { ref mut props, .. } => Some(props),
+            _ => None,
         }
     }
 
@@ -153,6 +187,7 @@ impl View {
         match self {
             &View::Text(_) => None,
             &View::Data { ref children, .. } => Some(children),
+            &View::Fragment(ref children) => Some(children),
         }
     }
     #[inline]
@@ -162,6 +197,7 @@ impl View {
             &mut View::Data {
                 ref mut children, ..
             } => Some(children),
+            &mut View::Fragment(ref mut children) => Some(children),
         }
     }
     #[inline]
@@ -179,6 +215,7 @@ impl View {
                 props: props.clone(),
                 children: Children::new(),
             },
+            &View::Fragment(_) => View::Fragment(Children::new()),
         }
     }
 }
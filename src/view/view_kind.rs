@@ -8,6 +8,8 @@ use super::Component;
 pub enum ViewKind {
     String(String),
     Component(Arc<dyn Component>),
+    /// renders its children into the host container with this id
+    Portal(String),
 }
 
 unsafe impl Sync for ViewKind {}
@@ -50,11 +52,15 @@ impl PartialEq for ViewKind {
         match self {
             &ViewKind::String(ref a) => match other {
                 &ViewKind::String(ref b) => a == b,
-                &ViewKind::Component(_) => false,
+                _ => false,
             },
             &ViewKind::Component(ref a) => match other {
-                &ViewKind::String(_) => false,
                 &ViewKind::Component(ref b) => a.type_id() == b.type_id(),
+                _ => false,
+            },
+            &ViewKind::Portal(ref a) => match other {
+                &ViewKind::Portal(ref b) => a == b,
+                _ => false,
             },
         }
     }
@@ -64,7 +70,7 @@ impl PartialEq<str> for ViewKind {
     fn eq(&self, other: &str) -> bool {
         match self {
             &ViewKind::String(ref a) => a == other,
-            &ViewKind::Component(_) => false,
+            _ => false,
         }
     }
 }
@@ -75,6 +81,7 @@ impl fmt::Debug for ViewKind {
         match self {
             &ViewKind::String(ref string) => f.write_str(string),
             &ViewKind::Component(ref component) => write!(f, "Component({})", component.name()),
+            &ViewKind::Portal(ref target) => write!(f, "Portal({})", target),
         }
     }
 }
@@ -91,14 +98,21 @@ impl ViewKind {
     pub fn is_string(&self) -> bool {
         match self {
             &ViewKind::String(_) => true,
-            &ViewKind::Component(_) => false,
+            _ => false,
         }
     }
     #[inline]
     pub fn is_component(&self) -> bool {
         match self {
-            &ViewKind::String(_) => false,
             &ViewKind::Component(_) => true,
+            _ => false,
+        }
+    }
+    #[inline]
+    pub fn is_portal(&self) -> bool {
+        match self {
+            &ViewKind::Portal(_) => true,
+            _ => false,
         }
     }
     #[inline]
@@ -106,6 +120,7 @@ impl ViewKind {
         match self {
             &ViewKind::String(_) => TypeId::of::<String>(),
             &ViewKind::Component(ref component) => (&**component).type_id(),
+            &ViewKind::Portal(_) => TypeId::of::<ViewKind>(),
         }
     }
     #[inline]
@@ -113,6 +128,7 @@ impl ViewKind {
         match self {
             ViewKind::String(string) => string,
             ViewKind::Component(_) => panic!("ViewKind::Component can not be a String"),
+            ViewKind::Portal(_) => panic!("ViewKind::Portal can not be a String"),
         }
     }
 }
@@ -0,0 +1,217 @@
+extern crate messenger;
+extern crate serde_json;
+extern crate tokio;
+#[macro_use]
+extern crate virtual_view;
+
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex};
+
+use serde_json::{from_value, Value};
+use tokio::executor::current_thread;
+use virtual_view::{
+    Children, Component, EventManager, Instance, Patch, Prop, Props, RawView, Renderer,
+    TestRenderer, Transaction, Updater, View,
+};
+
+fn increment(updater: &Updater, key: &'static str) {
+    updater.set_state(move |current| {
+        let mut next = current.clone();
+        next.update(key, |count| {
+            if let Some(c) = count.number() {
+                *count = (c + 1.0).into();
+            }
+        });
+        next
+    });
+}
+
+struct Child;
+
+impl Component for Child {
+    fn name(&self) -> &'static str {
+        "Child"
+    }
+    fn initial_state(&self, _: &Props) -> Props {
+        props! {
+            "clicks": 0,
+        }
+    }
+    fn render(&self, instance: &Instance, props: &Props, _: &Children) -> View {
+        view! {
+            <div class="Child">
+                <p>{ format!("Clicks {}", instance.state.get("clicks")) }</p>
+                <button onclick={ block {
+                    let updater = instance.updater.clone();
+                    let onchange = props.get("onchange").clone();
+                    move |e: &mut Props| {
+                        increment(&updater, "clicks");
+                        increment(&updater, "clicks");
+                        onchange.call(e);
+                        Prop::Null
+                    }
+                } }/>
+            </div>
+        }
+    }
+}
+
+struct Parent;
+
+impl Component for Parent {
+    fn name(&self) -> &'static str {
+        "Parent"
+    }
+    fn initial_state(&self, _: &Props) -> Props {
+        props! {
+            "count": 0,
+        }
+    }
+    fn render(&self, instance: &Instance, _: &Props, _: &Children) -> View {
+        view! {
+            <div class="Parent">
+                <p>{ format!("Count {}", instance.state.get("count")) }</p>
+                <{Child} onchange={ block {
+                    let updater = instance.updater.clone();
+                    move |_: &mut Props| {
+                        increment(&updater, "count");
+                        Prop::Null
+                    }
+                } }/>
+            </div>
+        }
+    }
+}
+
+#[test]
+fn test_batch() {
+    let (server, client, future) = messenger::unbounded_channel::<Value>();
+
+    let transactions: Arc<Mutex<Vec<Transaction>>> = Arc::new(Mutex::new(Vec::new()));
+    let client_transactions = transactions.clone();
+
+    let _ = client.on("virtual_view.transaction", move |t| {
+        client_transactions
+            .lock()
+            .unwrap()
+            .push(from_value(t.clone()).unwrap());
+        None
+    });
+
+    let renderer = Renderer::new(view! { <{Parent}/> }, EventManager::new(), server);
+    let root_id = renderer.root_id().clone();
+
+    renderer.dispatch(
+        &format!("{}.1.1", root_id),
+        &mut props! { "name": "onclick" },
+    );
+    client.close();
+
+    current_thread::run(|_| {
+        let _ = current_thread::spawn(future);
+    });
+
+    let transactions = transactions.lock().unwrap();
+    assert_eq!(transactions.len(), 2);
+
+    let patches = transactions[1].patches();
+    assert_eq!(patches.len(), 2);
+    assert_eq!(
+        patches[&format!("{}.0.0", root_id)],
+        vec![Patch::Replace(
+            RawView::Text("Count 0".into()),
+            RawView::Text("Count 1".into())
+        )]
+    );
+    assert_eq!(
+        patches[&format!("{}.1.0.0", root_id)],
+        vec![Patch::Replace(
+            RawView::Text("Clicks 0".into()),
+            RawView::Text("Clicks 2".into())
+        )]
+    );
+}
+
+#[test]
+fn test_batch_keeps_state_on_parent_render() {
+    let test_renderer = TestRenderer::new(view! { <div><{Child}/></div> });
+    let root_id = test_renderer.root_id().clone();
+    let text_id = format!("{}.0.0.0", root_id);
+
+    test_renderer.simulate(&format!("{}.0.1", root_id), "onclick", Props::new());
+    assert_eq!(
+        test_renderer.view(&text_id),
+        Some(RawView::Text("Clicks 2".into()))
+    );
+
+    // receiving new props without a queued state change keeps the current state
+    test_renderer.render(view! { <div><{Child} label="next"/></div> });
+    assert_eq!(
+        test_renderer.view(&text_id),
+        Some(RawView::Text("Clicks 2".into()))
+    );
+}
+
+struct Mounted(Arc<Mutex<usize>>);
+
+impl Component for Mounted {
+    fn name(&self) -> &'static str {
+        "Mounted"
+    }
+    fn did_mount(&self, _: &Instance) {
+        *self.0.lock().unwrap() += 1;
+    }
+    fn render(&self, _: &Instance, _: &Props, _: &Children) -> View {
+        view! { <p/> }
+    }
+}
+
+struct List(Arc<Mutex<usize>>);
+
+impl Component for List {
+    fn name(&self) -> &'static str {
+        "List"
+    }
+    fn render(&self, _: &Instance, _: &Props, _: &Children) -> View {
+        view! {
+            <ul>
+                <{Mounted(self.0.clone())}/>
+                <{Mounted(self.0.clone())}/>
+            </ul>
+        }
+    }
+}
+
+#[test]
+fn test_batch_mounts_component_children_once() {
+    let mounts = Arc::new(Mutex::new(0));
+    let test_renderer = TestRenderer::new(view! { <{List(mounts.clone())}/> });
+
+    assert_eq!(*mounts.lock().unwrap(), 2);
+    assert_eq!(test_renderer.take_transactions().len(), 1);
+    assert_eq!(
+        test_renderer
+            .tree()
+            .and_then(|tree| tree.children().map(|c| c.len())),
+        Some(2)
+    );
+}
+
+#[test]
+fn test_batch_after_handler_panic() {
+    let test_renderer = TestRenderer::new(view! {
+        <button onclick={ |_: &mut Props| -> Prop { panic!("handler failed") } }/>
+    });
+    let button_id = test_renderer.root_id().clone();
+    test_renderer.take_transactions();
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        test_renderer.simulate(&button_id, "onclick", Props::new())
+    }));
+    assert!(result.is_err());
+
+    // the renderer is not left processing, so later renders still go through
+    test_renderer.render(view! { <p/> });
+    assert_eq!(test_renderer.take_transactions().len(), 1);
+    assert_eq!(test_renderer.find_by_kind("p").len(), 1);
+}
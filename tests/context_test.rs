@@ -0,0 +1,101 @@
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+
+use virtual_view::{memo, Children, Component, Instance, Props, RawView, TestRenderer, View};
+
+type Log = Arc<Mutex<Vec<&'static str>>>;
+
+struct Provider;
+
+impl Component for Provider {
+    fn name(&self) -> &'static str {
+        "Provider"
+    }
+    fn context(&self, props: &Props) -> Props {
+        props! {
+            "theme": props.get("theme"),
+        }
+    }
+    fn render(&self, _: &Instance, _: &Props, children: &Children) -> View {
+        view! {
+            <div>{ each children.iter() }</div>
+        }
+    }
+}
+
+struct Middle(Log);
+
+impl Component for Middle {
+    fn name(&self) -> &'static str {
+        "Middle"
+    }
+    fn inherit_context(&self, mut context: Props, parent_context: &Props) -> Props {
+        context.extend(parent_context.clone());
+        context
+    }
+    fn render(&self, _: &Instance, _: &Props, _: &Children) -> View {
+        self.0.lock().unwrap().push("middle");
+
+        view! {
+            <section>
+                <{Consumer(self.0.clone())}/>
+            </section>
+        }
+    }
+}
+
+struct Consumer(Log);
+
+impl Component for Consumer {
+    fn name(&self) -> &'static str {
+        "Consumer"
+    }
+    fn inherit_context(&self, mut context: Props, parent_context: &Props) -> Props {
+        context.extend(parent_context.clone());
+        context
+    }
+    fn render(&self, instance: &Instance, _: &Props, _: &Children) -> View {
+        self.0.lock().unwrap().push("consumer");
+
+        view! {
+            <p>{ instance.context.get("theme") }</p>
+        }
+    }
+}
+
+fn app(log: &Log, theme: &str) -> View {
+    view! {
+        <{Provider} theme={ theme }>
+            <{memo(Middle(log.clone()))}/>
+        </{Provider}>
+    }
+}
+
+#[test]
+fn test_context_update() {
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(app(&log, "dark"));
+    let text_id = format!("{}.0.0.0", test_renderer.root_id());
+
+    assert_eq!(
+        test_renderer.view(&text_id),
+        Some(RawView::Text("dark".into()))
+    );
+    assert_eq!(*log.lock().unwrap(), vec!["middle", "consumer"]);
+    log.lock().unwrap().clear();
+
+    test_renderer.render(app(&log, "light"));
+
+    assert_eq!(
+        test_renderer.view(&text_id),
+        Some(RawView::Text("light".into()))
+    );
+    // the memoized middle component does not render but its consumer does
+    assert_eq!(*log.lock().unwrap(), vec!["consumer"]);
+    log.lock().unwrap().clear();
+
+    test_renderer.render(app(&log, "light"));
+    assert!(log.lock().unwrap().is_empty());
+}
@@ -0,0 +1,195 @@
+extern crate messenger;
+extern crate serde_json;
+extern crate tokio;
+#[macro_use]
+extern crate virtual_view;
+
+use std::panic;
+use std::sync::{Arc, Mutex};
+
+use serde_json::{from_value, Value};
+use tokio::executor::current_thread;
+use virtual_view::{
+    Children, Component, EventManager, Instance, Patch, Prop, Props, RawView, Ref, RenderError,
+    Renderer, TestRenderer, Transaction, View,
+};
+
+struct Buggy;
+
+impl Component for Buggy {
+    fn name(&self) -> &'static str {
+        "Buggy"
+    }
+    fn render(&self, _: &Instance, props: &Props, _: &Children) -> View {
+        if props.get("explode").is_true() {
+            panic!("exploded");
+        }
+        view! { <p class="Buggy">{"Fine"}</p> }
+    }
+}
+
+struct Boundary;
+
+impl Component for Boundary {
+    fn name(&self) -> &'static str {
+        "Boundary"
+    }
+    fn catch_error(&self, _: &Instance, error: &RenderError) -> Option<View> {
+        Some(view! {
+            <p class="Error">{ format!("{}: {}", error.name(), error.message()) }</p>
+        })
+    }
+    fn render(&self, _: &Instance, _: &Props, children: &Children) -> View {
+        view! { <div class="Boundary">{ each children }</div> }
+    }
+}
+
+struct App;
+
+impl Component for App {
+    fn name(&self) -> &'static str {
+        "App"
+    }
+    fn initial_state(&self, props: &Props) -> Props {
+        props! {
+            "explode": props.get("explode").clone(),
+        }
+    }
+    fn render(&self, instance: &Instance, _: &Props, _: &Children) -> View {
+        view! {
+            <div class="App" onclick={ block {
+                let updater = instance.updater.clone();
+                move |_: &mut Props| {
+                    updater.set_state(|current| {
+                        let mut next = current.clone();
+                        let explode = current.get("explode").is_true();
+                        next.insert("explode", !explode);
+                        next
+                    });
+                    Prop::Null
+                }
+            } }>
+                <{Boundary}>
+                    <{Buggy} explode={ instance.state.get("explode").clone() }/>
+                </{Boundary}>
+            </div>
+        }
+    }
+}
+
+fn error_view() -> RawView {
+    RawView::from(view! { <p class="Error">{"Buggy: exploded"}</p> })
+}
+
+#[test]
+fn test_error_boundary() {
+    let (server, client, future) = messenger::unbounded_channel::<Value>();
+
+    let transactions: Arc<Mutex<Vec<Transaction>>> = Arc::new(Mutex::new(Vec::new()));
+    let client_transactions = transactions.clone();
+    let _ = client.on("virtual_view.transaction", move |t| {
+        client_transactions
+            .lock()
+            .unwrap()
+            .push(from_value(t.clone()).unwrap());
+        None
+    });
+
+    let renderer = Renderer::new(view! { <{App} explode=true/> }, EventManager::new(), server);
+    let root_id = renderer.root_id().clone();
+
+    renderer.dispatch(&root_id, &mut props! { "name": "onclick" });
+    renderer.dispatch(&root_id, &mut props! { "name": "onclick" });
+    client.close();
+
+    current_thread::run(|_| {
+        let _ = current_thread::spawn(future);
+    });
+
+    let transactions = transactions.lock().unwrap();
+    let boundary_id = format!("{}.0", root_id);
+
+    match &transactions[0].patches()[&root_id][0] {
+        &Patch::Mount(ref view) => assert_eq!(&view.children().unwrap()[0], &error_view()),
+        patch => panic!("expected mount patch got {:?}", patch),
+    }
+    assert!(transactions[1].patches()[&boundary_id][0].is_replace());
+
+    match &transactions[2].patches()[&boundary_id][0] {
+        &Patch::Replace(_, ref next) => assert_eq!(next, &error_view()),
+        patch => panic!("expected replace patch got {:?}", patch),
+    }
+}
+
+#[test]
+fn test_uncaught_render_error() {
+    let (server, _client, _future) = messenger::unbounded_channel::<Value>();
+    let renderer = Renderer::new_empty(EventManager::new(), server);
+
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        renderer.mount(view! { <{Buggy} explode=true/> });
+    }));
+    let error = result.unwrap_err().downcast::<RenderError>().unwrap();
+    assert_eq!(error.name(), "Buggy");
+    assert_eq!(error.id(), renderer.root_id());
+
+    renderer.mount(view! { <{Buggy} explode=false/> });
+    renderer.unmount();
+}
+
+type Log = Arc<Mutex<Vec<&'static str>>>;
+
+struct Tracked(Log, Arc<Mutex<Option<Ref>>>);
+
+impl Component for Tracked {
+    fn name(&self) -> &'static str {
+        "Tracked"
+    }
+    fn will_unmount(&self, _: &Instance) {
+        self.0.lock().unwrap().push("will_unmount");
+    }
+    fn did_unmount(&self, _: &Instance) {
+        self.0.lock().unwrap().push("did_unmount");
+    }
+    fn render(&self, instance: &Instance, _: &Props, _: &Children) -> View {
+        let log = self.0.clone();
+        instance.use_effect(Vec::<Prop>::new(), move || {
+            log.lock().unwrap().push("effect");
+            move || log.lock().unwrap().push("cleanup")
+        });
+
+        let input_ref = instance.use_ref();
+        *self.1.lock().unwrap() = Some(input_ref.clone());
+
+        view! { <input ref={ input_ref }/> }
+    }
+}
+
+fn tracked(log: &Log, input_ref: &Arc<Mutex<Option<Ref>>>, explode: bool) -> View {
+    view! {
+        <{Boundary}>
+            <{Tracked(log.clone(), input_ref.clone())}/>
+            <{Buggy} explode={ explode }/>
+        </{Boundary}>
+    }
+}
+
+#[test]
+fn test_error_boundary_unmounts_removed_components() {
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+    let input_ref = Arc::new(Mutex::new(None));
+    let test_renderer = TestRenderer::new(tracked(&log, &input_ref, false));
+
+    let input_ref = input_ref.lock().unwrap().take().unwrap();
+    assert!(input_ref.is_mounted());
+
+    // the boundary drops the mounted children for its fallback
+    test_renderer.render(tracked(&log, &Arc::new(Mutex::new(None)), true));
+
+    assert_eq!(test_renderer.find_by_prop("class", "Error").len(), 1);
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec!["effect", "will_unmount", "cleanup", "did_unmount"]
+    );
+    assert!(!input_ref.is_mounted());
+}
@@ -0,0 +1,132 @@
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+
+use virtual_view::{Listener, Prop, Props, TestRenderer, View};
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+fn handler(log: &Log, tag: &'static str) -> Prop {
+    let log = log.clone();
+
+    Prop::from(move |e: &mut Props| {
+        log.lock()
+            .unwrap()
+            .push(format!("{} {}", tag, e.get("phase")));
+        Prop::Null
+    })
+}
+
+#[test]
+fn test_listener_from_key() {
+    let listener = Listener::from_key("onscroll_passive_capture");
+    assert_eq!(listener.name(), "onscroll");
+    assert!(listener.capture());
+    assert!(listener.passive());
+    assert!(!listener.once());
+
+    let listener = Listener::from_key("onclick");
+    assert_eq!(listener, Listener::new("onclick"));
+}
+
+#[test]
+fn test_event_options_once() {
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(view! {
+        <button onclick={ handler(&log, "click") } onclick_once={ handler(&log, "once") }/>
+    });
+    let root_id = test_renderer.root_id().clone();
+
+    let transactions = test_renderer.take_transactions();
+    assert!(transactions[0].events()[&root_id]["onclick_once"]);
+    assert_eq!(
+        transactions[0].listener(&root_id, "onclick"),
+        Listener::new("onclick")
+    );
+    assert_eq!(
+        transactions[0].listener(&root_id, "onclick_once"),
+        Listener::from_key("onclick_once")
+    );
+    assert_eq!(
+        test_renderer
+            .listener(&root_id, "onclick_once")
+            .map(|l| l.once()),
+        Some(true)
+    );
+
+    test_renderer.simulate(&root_id, "onclick", Props::new());
+    test_renderer.simulate(&root_id, "onclick", Props::new());
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec!["click target", "once target", "click target"]
+    );
+
+    // the host stops listening for the once handler in the next transaction
+    let transactions = test_renderer.take_transactions();
+    assert_eq!(transactions.len(), 1);
+    assert!(!transactions[0].events()[&root_id]["onclick_once"]);
+    assert!(!test_renderer.has_event(&root_id, "onclick_once"));
+    assert!(test_renderer.has_event(&root_id, "onclick"));
+
+    // the once handler stays removed when the view updates with a new function
+    test_renderer.render(view! {
+        <button onclick={ handler(&log, "click") } onclick_once={ handler(&log, "once") }/>
+    });
+    let result = test_renderer
+        .renderer()
+        .dispatch(&root_id, &mut props! { "name": "onclick" });
+    assert_eq!(result.handlers(), 1);
+    assert!(test_renderer.take_transactions().is_empty());
+}
+
+fn scroller(log: &Log, passive: bool) -> View {
+    view! {
+        <div onscroll={ props! {
+            "handler": handler(log, "div"),
+            "capture": true,
+            "passive": passive,
+        } }>
+            <p onscroll={ handler(log, "p") }/>
+        </div>
+    }
+}
+
+#[test]
+fn test_event_options_object() {
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(scroller(&log, true));
+    let root_id = test_renderer.root_id().clone();
+
+    let listener = test_renderer.listener(&root_id, "onscroll").unwrap();
+    assert_eq!(listener.name(), "onscroll");
+    assert!(listener.capture());
+    assert!(listener.passive());
+    assert!(!listener.once());
+
+    // handler objects are not sent as view props
+    assert_eq!(
+        test_renderer.tree().unwrap(),
+        test_renderer.view(&root_id).unwrap()
+    );
+    match test_renderer.tree().unwrap() {
+        virtual_view::RawView::Data { props, .. } => assert!(props.is_empty()),
+        view => panic!("expected a data view, got {:?}", view),
+    }
+
+    test_renderer.simulate(&format!("{}.0", root_id), "onscroll", Props::new());
+    assert_eq!(*log.lock().unwrap(), vec!["div capture", "p target"]);
+
+    test_renderer.take_transactions();
+    test_renderer.render(scroller(&log, false));
+
+    let transactions = test_renderer.take_transactions();
+    assert_eq!(transactions.len(), 1);
+    assert!(transactions[0].patches().is_empty());
+
+    assert!(transactions[0].events()[&root_id]["onscroll"]);
+    let listener = transactions[0].listener(&root_id, "onscroll");
+    assert!(listener.capture());
+    assert!(!listener.passive());
+}
@@ -0,0 +1,112 @@
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+
+use virtual_view::{Prop, Props, TestRenderer, View};
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+fn handler(log: &Log, tag: &'static str, flag: Option<&'static str>) -> Prop {
+    let log = log.clone();
+
+    Prop::from(move |e: &mut Props| {
+        log.lock().unwrap().push(format!(
+            "{} {} {}",
+            tag,
+            e.get("phase"),
+            e.get("current_target_id")
+        ));
+
+        if let Some(flag) = flag {
+            // the propagation prop stops propagation when false
+            e.set(flag, flag != "propagation");
+        }
+
+        Prop::String(tag.into())
+    })
+}
+
+fn app(log: &Log, stop: &'static str, flag: &'static str) -> View {
+    let f = |tag: &'static str| handler(log, tag, if tag == stop { Some(flag) } else { None });
+
+    view! {
+        <div onclick={ f("div") } onclick_capture={ f("div_capture") }>
+            <p onclick={ f("p") } onclick_capture={ f("p_capture") }>
+                <button onclick={ f("button") } onclick_capture={ f("button_capture") }/>
+            </p>
+        </div>
+    }
+}
+
+fn dispatch(stop: &'static str, flag: &'static str) -> (String, Vec<String>, Props) {
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(app(&log, stop, flag));
+    let root_id = test_renderer.root_id().clone();
+
+    let event = test_renderer.simulate(&format!("{}.0.0", root_id), "onclick", Props::new());
+    let log = log.lock().unwrap().clone();
+
+    (root_id, log, event)
+}
+
+#[test]
+fn test_event_phases() {
+    let (root_id, log, event) = dispatch("", "");
+
+    assert_eq!(
+        log,
+        vec![
+            format!("div_capture capture {}", root_id),
+            format!("p_capture capture {}.0", root_id),
+            format!("button_capture target {}.0.0", root_id),
+            format!("button target {}.0.0", root_id),
+            format!("p bubble {}.0", root_id),
+            format!("div bubble {}", root_id),
+        ]
+    );
+    assert_eq!(
+        event.get("target_id"),
+        &Prop::String(format!("{}.0.0", root_id))
+    );
+}
+
+#[test]
+fn test_event_stop_propagation() {
+    let (_, log, _) = dispatch("p_capture", "stop_propagation");
+    assert_eq!(log.len(), 2);
+
+    let (_, log, _) = dispatch("button_capture", "stop_propagation");
+    assert_eq!(log.len(), 4);
+
+    let (_, log, _) = dispatch("button_capture", "stop_immediate_propagation");
+    assert_eq!(log.len(), 3);
+
+    let (_, log, _) = dispatch("p", "propagation");
+    assert_eq!(log.len(), 5);
+}
+
+#[test]
+fn test_event_dispatch_result() {
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(app(&log, "p", "prevent_default"));
+    let root_id = test_renderer.root_id().clone();
+
+    let result = test_renderer.renderer().dispatch(
+        &format!("{}.0.0", root_id),
+        &mut props! { "name": "onclick" },
+    );
+
+    assert_eq!(result.handlers(), 6);
+    assert!(result.default_prevented());
+    assert!(!result.propagation_stopped());
+    assert_eq!(result.value(), &Prop::String("div".into()));
+
+    let result = test_renderer.renderer().dispatch(
+        &format!("{}.0.0", root_id),
+        &mut props! { "name": "onfocus" },
+    );
+
+    assert_eq!(result.handlers(), 0);
+    assert!(!result.default_prevented());
+}
@@ -0,0 +1,59 @@
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+
+use virtual_view::{Children, Component, Instance, Prop, Props, TestRenderer, View};
+
+type Log = Arc<Mutex<Vec<f64>>>;
+
+struct Clicker(Log);
+
+impl Component for Clicker {
+    fn name(&self) -> &'static str {
+        "Clicker"
+    }
+    fn initial_state(&self, _: &Props) -> Props {
+        props! {
+            "clicks": 0,
+        }
+    }
+    fn render(&self, instance: &Instance, _: &Props, _: &Children) -> View {
+        let clicks = instance.state.get("clicks").number().unwrap();
+
+        view! {
+            <button onclick={ block {
+                let log = self.0.clone();
+                let updater = instance.updater.clone();
+                move |_: &mut Props| {
+                    log.lock().unwrap().push(clicks);
+                    updater.set_state(move |_| props! { "clicks": clicks + 1.0 });
+                    Prop::Null
+                }
+            } }>{ format!("Clicks {}", clicks) }</button>
+        }
+    }
+}
+
+#[test]
+fn test_event_replace() {
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(view! { <{Clicker(log.clone())}/> });
+    let root_id = test_renderer.root_id().clone();
+
+    test_renderer.take_transactions();
+
+    test_renderer.simulate(&root_id, "onclick", Props::new());
+    test_renderer.simulate(&root_id, "onclick", Props::new());
+    test_renderer.simulate(&root_id, "onclick", Props::new());
+
+    assert_eq!(*log.lock().unwrap(), vec![0.0, 1.0, 2.0]);
+
+    let transactions = test_renderer.take_transactions();
+    assert_eq!(transactions.len(), 3);
+
+    for transaction in transactions {
+        assert!(transaction.events().is_empty());
+    }
+    assert!(test_renderer.has_event(&root_id, "onclick"));
+}
@@ -0,0 +1,85 @@
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+
+use virtual_view::{Event, Phase, Prop, Props, TestRenderer};
+
+#[test]
+fn test_typed_event() {
+    let events: Arc<Mutex<Vec<Event>>> = Arc::new(Mutex::new(Vec::new()));
+    let legacy: Arc<Mutex<Vec<Props>>> = Arc::new(Mutex::new(Vec::new()));
+
+    let test_renderer = TestRenderer::new(view! {
+        <div onclick={ block {
+            let legacy = legacy.clone();
+            move |e: &mut Props| {
+                legacy.lock().unwrap().push(e.clone());
+                Prop::Null
+            }
+        } }>
+            <button onclick={ Event::handler({
+                let events = events.clone();
+                move |e: &mut Event| {
+                    e.detail_mut().set("seen", true);
+                    e.prevent_default();
+                    events.lock().unwrap().push(e.clone());
+                    Prop::Null
+                }
+            }) }/>
+        </div>
+    });
+    let root_id = test_renderer.root_id().clone();
+    let button_id = format!("{}.0", root_id);
+
+    let mut event = Event::new("onclick", props! { "x": 10 });
+    let result = test_renderer
+        .renderer()
+        .dispatch_event(&button_id, &mut event);
+
+    assert!(result.default_prevented());
+    assert!(event.is_default_prevented());
+    assert_eq!(event.detail().get("seen"), &Prop::Boolean(true));
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].name(), "onclick");
+    assert_eq!(events[0].target_id(), &button_id);
+    assert_eq!(events[0].current_target_id(), &button_id);
+    assert_eq!(events[0].phase(), Phase::Target);
+    assert_eq!(events[0].detail().get("x"), &Prop::Number(10.0));
+    assert!(events[0].timestamp() > 0.0);
+
+    let legacy = legacy.lock().unwrap();
+    assert_eq!(legacy.len(), 1);
+    assert_eq!(legacy[0].get("seen"), &Prop::Boolean(true));
+    assert_eq!(legacy[0].get("x"), &Prop::Number(10.0));
+    assert_eq!(legacy[0].get("phase"), &Prop::String("bubble".into()));
+    assert_eq!(legacy[0].get("component_id"), &Prop::String(root_id));
+}
+
+#[test]
+fn test_typed_event_stop_propagation() {
+    let calls = Arc::new(Mutex::new(0));
+
+    let test_renderer = TestRenderer::new(view! {
+        <div onclick={ block {
+            let calls = calls.clone();
+            move |_: &mut Props| {
+                *calls.lock().unwrap() += 1;
+                Prop::Null
+            }
+        } }>
+            <button onclick={ Event::handler(|e: &mut Event| {
+                e.stop_propagation();
+                Prop::Null
+            }) }/>
+        </div>
+    });
+    let button_id = format!("{}.0", test_renderer.root_id());
+
+    let event = test_renderer.simulate(&button_id, "onclick", Props::new());
+
+    assert!(event.get("stop_propagation").is_true());
+    assert_eq!(*calls.lock().unwrap(), 0);
+}
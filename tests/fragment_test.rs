@@ -0,0 +1,244 @@
+extern crate messenger;
+extern crate serde_json;
+extern crate tokio;
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+
+use serde_json::{from_value, Value};
+use tokio::executor::current_thread;
+use virtual_view::{
+    render_to_string, Children, Component, EventManager, Instance, Patch, Prop, Props, RawView,
+    Renderer, TestRenderer, Transaction, View,
+};
+
+#[test]
+fn test_fragment_macro() {
+    let view = view! {
+        <>
+            <p>{"a"}</p>
+            <p>{"b"}</p>
+        </>
+    };
+
+    assert!(view.is_fragment());
+    assert_eq!(view.children().unwrap().len(), 2);
+}
+
+#[test]
+fn test_fragment_render_to_string() {
+    let items = view! {
+        <>
+            <li>{"b"}</li>
+            <>
+                <li>{"c"}</li>
+            </>
+        </>
+    };
+
+    assert_eq!(
+        render_to_string(view! { <ul><li>{"a"}</li>{ items }</ul> }),
+        "<ul><li>a</li><li>b</li><li>c</li></ul>"
+    );
+}
+
+struct List;
+
+impl Component for List {
+    fn name(&self) -> &'static str {
+        "List"
+    }
+    fn initial_state(&self, _: &Props) -> Props {
+        props! {
+            "reversed": false,
+        }
+    }
+    fn render(&self, instance: &Instance, _: &Props, _: &Children) -> View {
+        let items = if instance.state.get("reversed").is_true() {
+            view! {
+                <>
+                    <li key="c">{"c"}</li>
+                    <li key="b">{"b"}</li>
+                </>
+            }
+        } else {
+            view! {
+                <>
+                    <li key="b">{"b"}</li>
+                    <li key="c">{"c"}</li>
+                </>
+            }
+        };
+
+        view! {
+            <ul onclick={ block {
+                let updater = instance.updater.clone();
+                move |_: &mut Props| {
+                    updater.set_state(|current| {
+                        let mut next = current.clone();
+                        next.insert("reversed", true);
+                        next
+                    });
+                    Prop::Null
+                }
+            } }>
+                <li key="a">{"a"}</li>
+                { items }
+            </ul>
+        }
+    }
+}
+
+#[test]
+fn test_fragment_reconcile() {
+    let (server, client, future) = messenger::unbounded_channel::<Value>();
+
+    let transactions: Arc<Mutex<Vec<Transaction>>> = Arc::new(Mutex::new(Vec::new()));
+    let client_transactions = transactions.clone();
+    let _ = client.on("virtual_view.transaction", move |t| {
+        client_transactions
+            .lock()
+            .unwrap()
+            .push(from_value(t.clone()).unwrap());
+        None
+    });
+
+    let renderer = Renderer::new(view! { <{List}/> }, EventManager::new(), server);
+    let root_id = renderer.root_id().clone();
+
+    renderer.dispatch(&root_id, &mut props! { "name": "onclick" });
+    client.close();
+
+    current_thread::run(|_| {
+        let _ = current_thread::spawn(future);
+    });
+
+    let transactions = transactions.lock().unwrap();
+
+    match &transactions[0].patches()[&root_id][0] {
+        &Patch::Mount(ref view) => {
+            let children = view.children().unwrap();
+            assert_eq!(children.len(), 3);
+            assert_eq!(children[1].key(), Some(&"b".to_owned()));
+            assert_eq!(children[2].key(), Some(&"c".to_owned()));
+            assert_eq!(
+                children[2].children().unwrap()[0],
+                RawView::Text("c".into())
+            );
+        }
+        patch => panic!("expected mount patch got {:?}", patch),
+    }
+
+    match &transactions[1].patches()[&root_id][0] {
+        &Patch::Order(ref order) => {
+            assert_eq!(order.removes(), &[(2, Some("c".to_owned()))]);
+            assert_eq!(order.inserts(), &[(Some("c".to_owned()), 1)]);
+        }
+        patch => panic!("expected order patch got {:?}", patch),
+    }
+}
+
+struct Siblings;
+
+impl Component for Siblings {
+    fn name(&self) -> &'static str {
+        "Siblings"
+    }
+    fn initial_state(&self, _: &Props) -> Props {
+        props! {
+            "count": 1,
+        }
+    }
+    fn render(&self, instance: &Instance, _: &Props, _: &Children) -> View {
+        let count = instance.state.get("count").number().unwrap() as usize;
+        let items: Vec<View> = (0..count)
+            .map(|index| view! { <p>{ format!("{}", index) }</p> })
+            .collect();
+
+        view! {
+            <>
+                <button onclick={ block {
+                    let updater = instance.updater.clone();
+                    move |_: &mut Props| {
+                        updater.set_state(|current| {
+                            let mut next = current.clone();
+                            let count = next.get("count").number().unwrap();
+                            next.set("count", count + 1.0);
+                            next
+                        });
+                        Prop::Null
+                    }
+                } }/>
+                { View::Fragment(items) }
+            </>
+        }
+    }
+}
+
+fn text(text: &str) -> RawView {
+    RawView::Text(text.into())
+}
+
+#[test]
+fn test_fragment_component_root() {
+    let test_renderer = TestRenderer::new(view! { <div><{Siblings}/><span/></div> });
+    let root_id = test_renderer.root_id().clone();
+    let fragment_id = format!("{}.0", root_id);
+
+    let children = |tree: RawView| tree.children().unwrap().clone();
+    let tree = test_renderer.tree().unwrap();
+    assert_eq!(children(tree.clone()).len(), 2);
+    assert_eq!(children(tree)[1].kind(), Some(&"span".to_owned()));
+
+    let fragment = test_renderer.view(&fragment_id).unwrap();
+    assert_eq!(children(fragment.clone()).len(), 2);
+    assert_eq!(children(fragment)[1].children().unwrap(), &vec![text("0")]);
+
+    test_renderer.simulate(&format!("{}.0", fragment_id), "onclick", Props::new());
+    test_renderer.simulate(&format!("{}.0", fragment_id), "onclick", Props::new());
+
+    let fragment = test_renderer.view(&fragment_id).unwrap();
+    assert_eq!(children(fragment.clone()).len(), 4);
+    assert_eq!(children(fragment)[3].children().unwrap(), &vec![text("2")]);
+    assert_eq!(
+        test_renderer
+            .view(&format!("{}.1", root_id))
+            .unwrap()
+            .kind(),
+        Some(&"span".to_owned())
+    );
+
+    test_renderer.render(view! { <div><span/></div> });
+    assert_eq!(
+        test_renderer.view(&fragment_id).unwrap().kind(),
+        Some(&"span".to_owned())
+    );
+    assert_eq!(children(test_renderer.tree().unwrap()).len(), 1);
+}
+
+#[test]
+fn test_fragment_root() {
+    let test_renderer = TestRenderer::new(view! { <><p>{"a"}</p><{Siblings}/></> });
+    let root_id = test_renderer.root_id().clone();
+
+    let tree = test_renderer.tree().unwrap();
+    assert!(tree.kind().is_none());
+    assert_eq!(tree.children().unwrap().len(), 2);
+
+    test_renderer.render(view! { <><p>{"b"}</p></> });
+    assert_eq!(
+        test_renderer
+            .view(&format!("{}.0", root_id))
+            .unwrap()
+            .children()
+            .unwrap(),
+        &vec![text("b")]
+    );
+    assert!(test_renderer.view(&format!("{}.1", root_id)).is_none());
+
+    assert_eq!(
+        render_to_string(view! { <><p>{"a"}</p><div><{Siblings}/></div></> }),
+        "<p>a</p><div><button></button><p>0</p></div>"
+    );
+}
@@ -0,0 +1,84 @@
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+
+use virtual_view::{Children, Component, Instance, Prop, Props, RawView, TestRenderer, View};
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+struct Counter(Log);
+
+impl Component for Counter {
+    fn name(&self) -> &'static str {
+        "Counter"
+    }
+    fn render(&self, instance: &Instance, props: &Props, _: &Children) -> View {
+        let (count, set_count) = instance.use_state("count", props.get("start"));
+        let count = count.number().unwrap();
+
+        let double = instance.use_memo(vec![count], || {
+            self.0.lock().unwrap().push(format!("memo {}", count));
+            count * 2.0
+        });
+
+        instance.use_effect(vec![count], {
+            let log = self.0.clone();
+            move || {
+                log.lock().unwrap().push(format!("effect {}", count));
+                move || log.lock().unwrap().push(format!("cleanup {}", count))
+            }
+        });
+        instance.use_effect(Vec::<Prop>::new(), {
+            let log = self.0.clone();
+            move || {
+                log.lock().unwrap().push("mount".into());
+                move || log.lock().unwrap().push("unmount".into())
+            }
+        });
+
+        self.0.lock().unwrap().push(format!("render {}", count));
+
+        view! {
+            <button onclick={ move |_: &mut Props| {
+                set_count.update(|count| Prop::from(count.number().unwrap() + 1.0));
+                Prop::Null
+            } }>{ format!("{} {}", count, double) }</button>
+        }
+    }
+}
+
+fn take(log: &Log) -> Vec<String> {
+    log.lock().unwrap().drain(..).collect()
+}
+
+#[test]
+fn test_hooks() {
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(view! { <{Counter(log.clone())} start=1/> });
+    let root_id = test_renderer.root_id().clone();
+    let text_id = format!("{}.0", root_id);
+
+    assert_eq!(take(&log), vec!["memo 1", "render 1", "effect 1", "mount"]);
+    assert_eq!(
+        test_renderer.view(&text_id),
+        Some(RawView::Text("1 2".into()))
+    );
+
+    test_renderer.simulate(&root_id, "onclick", Props::new());
+    assert_eq!(
+        take(&log),
+        vec!["memo 2", "render 2", "cleanup 1", "effect 2"]
+    );
+    assert_eq!(
+        test_renderer.view(&text_id),
+        Some(RawView::Text("2 4".into()))
+    );
+
+    // new props do not change the state or the memoized value
+    test_renderer.render(view! { <{Counter(log.clone())} start=10/> });
+    assert_eq!(take(&log), vec!["render 2"]);
+
+    test_renderer.unmount();
+    assert_eq!(take(&log), vec!["cleanup 2", "unmount"]);
+}
@@ -0,0 +1,226 @@
+extern crate messenger;
+#[macro_use]
+extern crate serde_json;
+extern crate tokio;
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+
+use serde_json::{from_value, Value};
+use tokio::executor::current_thread;
+use virtual_view::{
+    Children, Component, EventManager, Instance, Patch, Prop, Props, RawView, Renderer,
+    Transaction, View,
+};
+
+struct Input;
+
+impl Component for Input {
+    fn name(&self) -> &'static str {
+        "Input"
+    }
+    fn initial_state(&self, _: &Props) -> Props {
+        props! {
+            "value": "",
+        }
+    }
+    fn render(&self, instance: &Instance, _: &Props, _: &Children) -> View {
+        view! {
+            <div>
+                <p>{ instance.state.get("value") }</p>
+                <input oninput={ block {
+                    let updater = instance.updater.clone();
+                    move |e: &mut Props| {
+                        let value = e.get("value").clone();
+                        updater.set_state(move |_| props! { "value": value.clone() });
+                        e.set("prevent_default", true);
+                        Prop::String("accepted".into())
+                    }
+                } }/>
+            </div>
+        }
+    }
+}
+
+#[test]
+fn test_host_event() {
+    let (server, client, future) = messenger::unbounded_channel::<Value>();
+
+    let transactions: Arc<Mutex<Vec<Transaction>>> = Arc::new(Mutex::new(Vec::new()));
+    let client_transactions = transactions.clone();
+
+    let _ = client.on("virtual_view.transaction", move |t| {
+        client_transactions
+            .lock()
+            .unwrap()
+            .push(from_value(t.clone()).unwrap());
+        None
+    });
+
+    let renderer = Renderer::new(view! { <{Input}/> }, EventManager::new(), server);
+    let root_id = renderer.root_id().clone();
+
+    let results: Arc<Mutex<Vec<Value>>> = Arc::new(Mutex::new(Vec::new()));
+    let client_results = results.clone();
+
+    let _ = client.send(
+        "virtual_view.event",
+        json!({
+            "id": format!("{}.1", root_id),
+            "name": "oninput",
+            "data": { "value": "hello" },
+        }),
+        move |result| client_results.lock().unwrap().extend(result),
+    );
+    client.close();
+
+    current_thread::run(|_| {
+        let _ = current_thread::spawn(future);
+    });
+
+    let results = results.lock().unwrap();
+    assert_eq!(
+        *results,
+        vec![json!({
+            "handlers": 1,
+            "default_prevented": true,
+            "propagation_stopped": false,
+            "value": "accepted",
+        })]
+    );
+
+    let transactions = transactions.lock().unwrap();
+    assert_eq!(transactions.len(), 2);
+    assert_eq!(
+        transactions[1].patches()[&format!("{}.0.0", root_id)],
+        vec![Patch::Replace(
+            RawView::Text("".into()),
+            RawView::Text("hello".into())
+        )]
+    );
+}
+
+#[test]
+fn test_host_event_shared_messenger() {
+    let (server, client, future) = messenger::unbounded_channel::<Value>();
+
+    let renderer = Renderer::new(view! { <{Input}/> }, EventManager::new(), server.clone());
+    let _other = Renderer::new(view! { <{Input}/> }, EventManager::new(), server.clone());
+    let _empty = Renderer::new_empty(EventManager::new(), server);
+    let root_id = renderer.root_id().clone();
+
+    let results: Arc<Mutex<Vec<Value>>> = Arc::new(Mutex::new(Vec::new()));
+    let client_results = results.clone();
+
+    // only the renderer the view belongs to answers
+    let _ = client.send(
+        "virtual_view.event",
+        json!({
+            "id": format!("{}.1", root_id),
+            "name": "oninput",
+            "data": { "value": "hello" },
+        }),
+        move |result| client_results.lock().unwrap().extend(result),
+    );
+    client.close();
+
+    current_thread::run(|_| {
+        let _ = current_thread::spawn(future);
+    });
+
+    let results = results.lock().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["handlers"], json!(1));
+}
+
+fn send_input(
+    client: &messenger::Messenger<Value>,
+    root_id: &str,
+    answers: &Arc<Mutex<Vec<Value>>>,
+) {
+    let client_answers = answers.clone();
+
+    let _ = client.send(
+        "virtual_view.event",
+        json!({
+            "id": format!("{}.1", root_id),
+            "name": "oninput",
+            "data": { "value": "hello" },
+        }),
+        move |answer| client_answers.lock().unwrap().push(Value::Array(answer)),
+    );
+}
+
+#[test]
+fn test_host_event_handlers_removed_on_unmount() {
+    let (server, client, future) = messenger::unbounded_channel::<Value>();
+
+    let renderer = Renderer::new(view! { <{Input}/> }, EventManager::new(), server);
+    let answers = Arc::new(Mutex::new(Vec::new()));
+
+    renderer.unmount();
+    send_input(&client, renderer.root_id(), &answers);
+    client.close();
+
+    current_thread::run(|_| {
+        let _ = current_thread::spawn(future);
+    });
+
+    // the messenger only answers names something is subscribed to
+    assert!(answers.lock().unwrap().is_empty());
+}
+
+struct Closer(Arc<Mutex<Option<Renderer>>>);
+
+impl Component for Closer {
+    fn name(&self) -> &'static str {
+        "Closer"
+    }
+    fn render(&self, _: &Instance, _: &Props, _: &Children) -> View {
+        view! {
+            <div>
+                <p/>
+                <input oninput={ block {
+                    let renderer = self.0.clone();
+                    move |_: &mut Props| {
+                        if let Some(renderer) = renderer.lock().unwrap().take() {
+                            renderer.unmount();
+                        }
+                        Prop::Null
+                    }
+                } }/>
+            </div>
+        }
+    }
+}
+
+#[test]
+fn test_host_event_unmount_in_handler() {
+    let (server, client, future) = messenger::unbounded_channel::<Value>();
+
+    let slot = Arc::new(Mutex::new(None));
+    let renderer = Renderer::new(
+        view! { <{Closer(slot.clone())}/> },
+        EventManager::new(),
+        server,
+    );
+    let root_id = renderer.root_id().clone();
+    *slot.lock().unwrap() = Some(renderer.clone());
+
+    let answers = Arc::new(Mutex::new(Vec::new()));
+
+    send_input(&client, &root_id, &answers);
+    send_input(&client, &root_id, &answers);
+    client.close();
+
+    current_thread::run(|_| {
+        let _ = current_thread::spawn(future);
+    });
+
+    // the handlers can not be removed while they run, after the unmount they ignore events
+    let answers = answers.lock().unwrap();
+    assert_eq!(answers.len(), 2);
+    assert_eq!(answers[0][0]["handlers"], json!(1));
+    assert_eq!(answers[1], json!([]));
+}
@@ -0,0 +1,161 @@
+#[macro_use]
+extern crate virtual_view;
+
+use std::io;
+
+use virtual_view::{
+    render_to_string, render_to_writer, Children, Component, Instance, Prop, Props, RenderError,
+    View,
+};
+
+struct Item;
+
+impl Component for Item {
+    fn name(&self) -> &'static str {
+        "Item"
+    }
+    fn inherit_context(&self, mut context: Props, parent_context: &Props) -> Props {
+        context.extend(parent_context);
+        context
+    }
+    fn render(&self, instance: &Instance, props: &Props, _: &Children) -> View {
+        view! {
+            <li class={ instance.context.get("class").clone() }>{ props.get("text").clone() }</li>
+        }
+    }
+}
+
+struct List;
+
+impl Component for List {
+    fn name(&self) -> &'static str {
+        "List"
+    }
+    fn initial_state(&self, _: &Props) -> Props {
+        props! {
+            "title": "Items & <more>",
+        }
+    }
+    fn context(&self, _: &Props) -> Props {
+        props! {
+            "class": "Item",
+        }
+    }
+    fn render(&self, instance: &Instance, _: &Props, _: &Children) -> View {
+        view! {
+            <div class="List" onclick={ |_: &mut Props| Prop::Null }>
+                <h1>{ instance.state.get("title").clone() }</h1>
+                <input disabled=true checked=false/>
+                <ul style={{ "color": "#F00" }}>
+                    <{Item} text="a \"quoted\" item"/>
+                </ul>
+            </div>
+        }
+    }
+}
+
+#[test]
+fn test_render_to_string() {
+    let html = render_to_string(view! { <{List}/> });
+
+    assert_eq!(
+        html,
+        "<div class=\"List\">\
+         <h1>Items &amp; &lt;more&gt;</h1>\
+         <input disabled>\
+         <ul style=\"color:#F00;\">\
+         <li class=\"Item\">a \"quoted\" item</li>\
+         </ul>\
+         </div>"
+    );
+}
+
+#[test]
+fn test_render_to_writer() {
+    let mut out = Vec::new();
+
+    render_to_writer(view! { <p title="a<b">{"text"}</p> }, &mut out).unwrap();
+
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "<p title=\"a&lt;b\">text</p>"
+    );
+}
+
+struct Thrower;
+
+impl Component for Thrower {
+    fn name(&self) -> &'static str {
+        "Thrower"
+    }
+    fn render(&self, _: &Instance, _: &Props, _: &Children) -> View {
+        panic!("failed to render")
+    }
+}
+
+struct Boundary;
+
+impl Component for Boundary {
+    fn name(&self) -> &'static str {
+        "Boundary"
+    }
+    fn catch_error(&self, _: &Instance, error: &RenderError) -> Option<View> {
+        Some(view! { <p class="Error">{ error.name() }</p> })
+    }
+    fn render(&self, _: &Instance, _: &Props, children: &Children) -> View {
+        view! { <div class="Boundary">{ each children }</div> }
+    }
+}
+
+#[test]
+fn test_render_to_string_error_boundary() {
+    assert_eq!(
+        render_to_string(view! {
+            <main>
+                <{Boundary}>
+                    <span/>
+                    <{Thrower}/>
+                </{Boundary}>
+            </main>
+        }),
+        "<main><p class=\"Error\">Thrower</p></main>"
+    );
+}
+
+#[test]
+fn test_render_to_string_numbers() {
+    let html = render_to_string(view! {
+        <img width={100} height={1.5} style={{ "order": 2, "opacity": 0.5 }}/>
+    });
+
+    assert_eq!(
+        html,
+        "<img height=\"1.5\" style=\"opacity:0.5;order:2;\" width=\"100\">"
+    );
+}
+
+#[test]
+fn test_render_to_string_skips_invalid_attribute_names() {
+    let mut props = Props::new();
+    props.insert("title", "ok");
+    props.insert("onload=\"alert(1)\"", "a");
+    props.insert("a b", "b");
+    props.insert("a>", "c");
+    props.insert("/", "d");
+    props.insert("", "e");
+
+    let html = render_to_string(View::new("div".into(), props, Children::new()));
+
+    assert_eq!(html, "<div title=\"ok\"></div>");
+}
+
+#[test]
+fn test_render_to_writer_invalid_tag_name() {
+    let mut out = Vec::new();
+    let view = View::new_data("div><script");
+
+    let error = render_to_writer(view, &mut out).unwrap_err();
+
+    assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    assert!(out.is_empty());
+}
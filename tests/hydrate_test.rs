@@ -0,0 +1,91 @@
+extern crate messenger;
+extern crate serde_json;
+extern crate tokio;
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+
+use serde_json::{from_value, Value};
+use tokio::executor::current_thread;
+use virtual_view::{
+    render_to_raw_view, Children, Component, EventManager, Instance, Mismatch, Prop, Props,
+    RawView, Renderer, Transaction, View,
+};
+
+struct App;
+
+impl Component for App {
+    fn name(&self) -> &'static str {
+        "App"
+    }
+    fn render(&self, _: &Instance, props: &Props, _: &Children) -> View {
+        view! {
+            <div class="App">
+                <button onclick={ |_: &mut Props| Prop::Null }>{"Click Me!"}</button>
+                <p>{ props.get("text").clone() }</p>
+            </div>
+        }
+    }
+}
+
+fn hydrate(view: View, existing: RawView) -> (Vec<Transaction>, Vec<Mismatch>) {
+    let (server, client, future) = messenger::unbounded_channel::<Value>();
+
+    let transactions: Arc<Mutex<Vec<Transaction>>> = Arc::new(Mutex::new(Vec::new()));
+    let mismatches: Arc<Mutex<Vec<Mismatch>>> = Arc::new(Mutex::new(Vec::new()));
+
+    let client_transactions = transactions.clone();
+    let _ = client.on("virtual_view.transaction", move |t| {
+        client_transactions
+            .lock()
+            .unwrap()
+            .push(from_value(t.clone()).unwrap());
+        None
+    });
+    let client_mismatches = mismatches.clone();
+    let _ = client.on("virtual_view.mismatches", move |m| {
+        client_mismatches
+            .lock()
+            .unwrap()
+            .extend(from_value::<Vec<Mismatch>>(m.clone()).unwrap());
+        None
+    });
+
+    let renderer = Renderer::new_empty(EventManager::new(), server);
+    renderer.hydrate(view, existing);
+    client.close();
+
+    current_thread::run(|_| {
+        let _ = current_thread::spawn(future);
+    });
+
+    let transactions = transactions.lock().unwrap().clone();
+    let mismatches = mismatches.lock().unwrap().clone();
+
+    (transactions, mismatches)
+}
+
+#[test]
+fn test_hydrate_match() {
+    let existing = render_to_raw_view(view! { <{App} text="Hello"/> });
+    let (transactions, mismatches) = hydrate(view! { <{App} text="Hello"/> }, existing);
+
+    assert!(mismatches.is_empty());
+    assert_eq!(transactions.len(), 1);
+    assert!(transactions[0].patches().is_empty());
+    assert!(transactions[0].events().values().any(|e| e["onclick"]));
+}
+
+#[test]
+fn test_hydrate_mismatch() {
+    let existing = render_to_raw_view(view! { <{App} text="Hello"/> });
+    let (transactions, mismatches) = hydrate(view! { <{App} text="World"/> }, existing);
+
+    assert_eq!(mismatches.len(), 1);
+    assert!(mismatches[0].is_text());
+
+    let id = mismatches[0].id();
+    assert!(transactions[0].patches()[id][0].is_replace());
+    assert!(!transactions[0].patches().values().any(|p| p[0].is_mount()));
+}
@@ -0,0 +1,106 @@
+extern crate messenger;
+extern crate serde_json;
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+
+use serde_json::Value;
+use virtual_view::{Children, Component, EventManager, Instance, Prop, Props, Renderer, View};
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+struct Child(Log);
+
+impl Component for Child {
+    fn name(&self) -> &'static str {
+        "Child"
+    }
+    fn did_mount(&self, _: &Instance) {
+        self.0.lock().unwrap().push("Child did_mount".into());
+    }
+    fn did_update(&self, _: &Instance, _: &Props, _: &Props) {
+        self.0.lock().unwrap().push("Child did_update".into());
+    }
+    fn did_unmount(&self, _: &Instance) {
+        self.0.lock().unwrap().push("Child did_unmount".into());
+    }
+    fn render(&self, _: &Instance, props: &Props, _: &Children) -> View {
+        view! {
+            <p>{ props.get("count").clone() }</p>
+        }
+    }
+}
+
+struct Parent(Log);
+
+impl Component for Parent {
+    fn name(&self) -> &'static str {
+        "Parent"
+    }
+    fn initial_state(&self, _: &Props) -> Props {
+        props! {
+            "count": 0,
+        }
+    }
+    fn did_mount(&self, _: &Instance) {
+        self.0.lock().unwrap().push("Parent did_mount".into());
+    }
+    fn did_update(&self, instance: &Instance, _: &Props, prev_state: &Props) {
+        self.0.lock().unwrap().push(format!(
+            "Parent did_update {} -> {}",
+            prev_state.get("count"),
+            instance.state.get("count")
+        ));
+    }
+    fn did_unmount(&self, _: &Instance) {
+        self.0.lock().unwrap().push("Parent did_unmount".into());
+    }
+    fn render(&self, instance: &Instance, _: &Props, _: &Children) -> View {
+        view! {
+            <div onclick={ block {
+                let updater = instance.updater.clone();
+                move |_: &mut Props| {
+                    updater.set_state(|current| {
+                        let mut next = current.clone();
+                        next.insert("count", 1);
+                        next
+                    });
+                    Prop::Null
+                }
+            } }>
+                <{Child(self.0.clone())} count={ instance.state.get("count").clone() }/>
+            </div>
+        }
+    }
+}
+
+#[test]
+fn test_lifecycle() {
+    let (server, _client, _future) = messenger::unbounded_channel::<Value>();
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+
+    let renderer = Renderer::new(
+        view! { <{Parent(log.clone())}/> },
+        EventManager::new(),
+        server,
+    );
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec!["Child did_mount", "Parent did_mount"]
+    );
+    log.lock().unwrap().clear();
+
+    renderer.dispatch(renderer.root_id(), &mut props! { "name": "onclick" });
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec!["Child did_update", "Parent did_update 0 -> 1"]
+    );
+    log.lock().unwrap().clear();
+
+    renderer.unmount();
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec!["Child did_unmount", "Parent did_unmount"]
+    );
+}
@@ -0,0 +1,263 @@
+extern crate messenger;
+extern crate serde_json;
+extern crate tokio;
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+
+use serde_json::{from_value, Value};
+use tokio::executor::current_thread;
+use virtual_view::{
+    render_to_string, Children, Component, EventManager, Instance, Patch, Prop, Props, RawView,
+    Renderer, TestRenderer, Transaction, View,
+};
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+struct Modal(Log);
+
+impl Component for Modal {
+    fn name(&self) -> &'static str {
+        "Modal"
+    }
+    fn inherit_context(&self, _: Props, parent_context: &Props) -> Props {
+        parent_context.clone()
+    }
+    fn render(&self, instance: &Instance, props: &Props, _: &Children) -> View {
+        view! {
+            <div class={ instance.context.get("theme") }>
+                <button onclick={ block {
+                    let log = self.0.clone();
+                    let onclose = props.get("onclose").clone();
+                    move |e: &mut Props| {
+                        log.lock().unwrap().push("button".into());
+                        onclose.call(e);
+                        Prop::Null
+                    }
+                } }>{"Close"}</button>
+            </div>
+        }
+    }
+}
+
+struct App(Log);
+
+impl Component for App {
+    fn name(&self) -> &'static str {
+        "App"
+    }
+    fn initial_state(&self, _: &Props) -> Props {
+        props! {
+            "open": true,
+        }
+    }
+    fn context(&self, _: &Props) -> Props {
+        props! {
+            "theme": "dark",
+        }
+    }
+    fn render(&self, instance: &Instance, _: &Props, _: &Children) -> View {
+        let content = if instance.state.get("open").is_true() {
+            View::new_portal(
+                "modal",
+                vec![view! {
+                    <{Modal(self.0.clone())} onclose={ block {
+                        let updater = instance.updater.clone();
+                        move |_: &mut Props| {
+                            updater.set_state(|_| props! { "open": false });
+                            Prop::Null
+                        }
+                    } }/>
+                }],
+            )
+        } else {
+            view! { <p>{"Closed"}</p> }
+        };
+
+        view! {
+            <div class="App" onclick={ block {
+                let log = self.0.clone();
+                move |_: &mut Props| {
+                    log.lock().unwrap().push("app".into());
+                    Prop::Null
+                }
+            } }>
+                <p>{"Page"}</p>
+                {content}
+            </div>
+        }
+    }
+}
+
+fn run<F>(f: F) -> (String, Vec<Transaction>)
+where
+    F: FnOnce(&Renderer),
+{
+    let (server, client, future) = messenger::unbounded_channel::<Value>();
+
+    let transactions: Arc<Mutex<Vec<Transaction>>> = Arc::new(Mutex::new(Vec::new()));
+    let client_transactions = transactions.clone();
+
+    let _ = client.on("virtual_view.transaction", move |t| {
+        client_transactions
+            .lock()
+            .unwrap()
+            .push(from_value(t.clone()).unwrap());
+        None
+    });
+
+    let renderer = Renderer::new_empty(EventManager::new(), server);
+    f(&renderer);
+    client.close();
+
+    current_thread::run(|_| {
+        let _ = current_thread::spawn(future);
+    });
+
+    let transactions = transactions.lock().unwrap().clone();
+    (renderer.root_id().clone(), transactions)
+}
+
+#[test]
+fn test_portal_mount() {
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+    let (root_id, transactions) = run(|renderer| renderer.mount(view! { <{App(log.clone())}/> }));
+
+    assert_eq!(transactions.len(), 1);
+
+    let patches = transactions[0].patches();
+    match &patches[&root_id][0] {
+        &Patch::Mount(RawView::Data { ref children, .. }) => {
+            assert_eq!(children.len(), 2);
+            assert_eq!(children[1], RawView::Text(String::new()));
+        }
+        patch => panic!("expected a mount patch, found {:?}", patch),
+    }
+
+    // the portal's children are in a fragment below the portal's own id
+    match &patches["modal"][0] {
+        &Patch::Insert(ref id, index, RawView::Fragment(ref children)) => {
+            assert_eq!(id, &format!("{}.1.0", root_id));
+            assert_eq!(index, 0);
+            assert_eq!(
+                children[0].props().unwrap().get("class"),
+                Some(&Value::from("dark"))
+            );
+        }
+        patch => panic!("expected an insert patch, found {:?}", patch),
+    }
+
+    assert!(transactions[0].events()[&format!("{}.1.0.0.0", root_id)]["onclick"]);
+}
+
+#[test]
+fn test_portal_event_bubbling() {
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+    let (root_id, transactions) = run(|renderer| {
+        renderer.mount(view! { <{App(log.clone())}/> });
+        renderer.dispatch(
+            &format!("{}.1.0.0.0", renderer.root_id()),
+            &mut props! { "name": "onclick" },
+        );
+    });
+    let button_id = format!("{}.1.0.0.0", root_id);
+
+    assert_eq!(*log.lock().unwrap(), vec!["button", "app"]);
+
+    assert_eq!(transactions.len(), 2);
+    assert_eq!(
+        transactions[1].removes().keys().collect::<Vec<_>>(),
+        vec![&format!("{}.1.0", root_id)]
+    );
+    assert_eq!(
+        transactions[1].patches()[&format!("{}.1", root_id)],
+        vec![Patch::Replace(
+            RawView::Text(String::new()),
+            RawView::Data {
+                kind: "p".into(),
+                key: None,
+                props: Default::default(),
+                children: vec![RawView::Text("Closed".into())],
+            }
+        )]
+    );
+    assert!(!transactions[1].events()[&button_id]["onclick"]);
+}
+
+#[test]
+fn test_portal_unmount() {
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+    let (root_id, transactions) = run(|renderer| {
+        renderer.mount(view! { <{App(log.clone())}/> });
+        renderer.unmount();
+        renderer.dispatch(
+            &format!("{}.1.0.0.0", renderer.root_id()),
+            &mut props! { "name": "onclick" },
+        );
+    });
+
+    assert!(log.lock().unwrap().is_empty());
+    assert_eq!(transactions.len(), 2);
+    assert!(transactions[1]
+        .removes()
+        .contains_key(&format!("{}.1.0", root_id)));
+}
+
+#[test]
+fn test_portal_render_to_string() {
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+
+    assert_eq!(
+        render_to_string(view! { <{App(log)}/> }),
+        "<div class=\"App\"><p>Page</p></div>"
+    );
+}
+
+fn toasts(texts: &[&str]) -> View {
+    let portals: Vec<View> = texts
+        .iter()
+        .map(|text| View::new_portal("toasts", vec![view! { <p>{ text.to_string() }</p> }]))
+        .collect();
+
+    view! { <div>{ View::Fragment(portals) }</div> }
+}
+
+#[test]
+fn test_portals_into_one_target() {
+    let test_renderer = TestRenderer::new(toasts(&["a", "b"]));
+    let root_id = test_renderer.root_id().clone();
+
+    let fragment = |text: &str| {
+        RawView::Fragment(vec![RawView::Data {
+            kind: "p".into(),
+            key: None,
+            props: Default::default(),
+            children: vec![RawView::Text(text.into())],
+        }])
+    };
+
+    assert_eq!(
+        test_renderer.container("toasts"),
+        Some(vec![fragment("a"), fragment("b")])
+    );
+
+    test_renderer.render(toasts(&["a", "c"]));
+    assert_eq!(
+        test_renderer.container("toasts"),
+        Some(vec![fragment("a"), fragment("c")])
+    );
+    assert_eq!(
+        test_renderer.view(&format!("{}.1.0.0.0", root_id)),
+        Some(RawView::Text("c".into()))
+    );
+
+    test_renderer.render(toasts(&["d"]));
+    assert_eq!(test_renderer.container("toasts"), Some(vec![fragment("d")]));
+
+    test_renderer.render(toasts(&["e", "f"]));
+    assert_eq!(
+        test_renderer.container("toasts"),
+        Some(vec![fragment("e"), fragment("f")])
+    );
+}
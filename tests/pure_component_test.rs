@@ -0,0 +1,102 @@
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+
+use virtual_view::{
+    memo, Children, Component, Instance, Prop, Props, PureComponent, TestRenderer, View,
+};
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+struct Child(&'static str, Log);
+
+impl Component for Child {
+    fn name(&self) -> &'static str {
+        "Child"
+    }
+    fn initial_state(&self, _: &Props) -> Props {
+        props! {
+            "value": 0,
+        }
+    }
+    fn render(&self, instance: &Instance, props: &Props, _: &Children) -> View {
+        self.1.lock().unwrap().push(self.0.into());
+
+        let value = instance.state.get("value").clone();
+
+        view! {
+            <button onset={ block {
+                let updater = instance.updater.clone();
+                move |e: &mut Props| {
+                    let value = e.get("value").clone();
+                    updater.set_state(move |_| props! { "value": value.clone() });
+                    Prop::Null
+                }
+            } }>{ format!("{} {}", props.get("label"), value) }</button>
+        }
+    }
+}
+
+struct Parent(Log);
+
+impl Component for Parent {
+    fn name(&self) -> &'static str {
+        "Parent"
+    }
+    fn initial_state(&self, _: &Props) -> Props {
+        props! {
+            "count": 0,
+        }
+    }
+    fn render(&self, instance: &Instance, _: &Props, _: &Children) -> View {
+        let count = instance.state.get("count").number().unwrap();
+
+        view! {
+            <div onclick={ block {
+                let updater = instance.updater.clone();
+                move |_: &mut Props| {
+                    updater.set_state(move |_| props! { "count": count + 1.0 });
+                    Prop::Null
+                }
+            } }>
+                <{Child("plain", self.0.clone())} label="plain"/>
+                <{memo(Child("pure", self.0.clone()))} label="pure"/>
+                <{memo(Child("count", self.0.clone()))} label="count" count={ count }/>
+                <{PureComponent::with_props_equal(
+                    Child("custom", self.0.clone()),
+                    |prev: &Props, next: &Props| prev.get("label") == next.get("label")
+                )} label="custom" count={ count }/>
+            </div>
+        }
+    }
+}
+
+#[test]
+fn test_pure_component() {
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(view! { <{Parent(log.clone())}/> });
+    let root_id = test_renderer.root_id().clone();
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec!["plain", "pure", "count", "custom"]
+    );
+    log.lock().unwrap().clear();
+
+    test_renderer.simulate(&root_id, "onclick", Props::new());
+    assert_eq!(*log.lock().unwrap(), vec!["plain", "count"]);
+    log.lock().unwrap().clear();
+
+    let pure_id = format!("{}.1", root_id);
+
+    test_renderer.simulate(&pure_id, "onset", props! { "value": 0 });
+    assert!(log.lock().unwrap().is_empty());
+
+    test_renderer.simulate(&pure_id, "onset", props! { "value": 1 });
+    assert_eq!(*log.lock().unwrap(), vec!["pure"]);
+    assert_eq!(
+        test_renderer.view(&format!("{}.1.0", root_id)),
+        Some(virtual_view::RawView::Text("pure 1".into()))
+    );
+}
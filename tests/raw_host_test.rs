@@ -0,0 +1,142 @@
+extern crate messenger;
+extern crate serde_json;
+extern crate tokio;
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+
+use serde_json::{from_value, Value};
+use tokio::executor::current_thread;
+use virtual_view::{
+    ApplyError, Children, Component, EventManager, Instance, Prop, Props, RawHost, RawView,
+    Renderer, Transaction, View,
+};
+
+struct List;
+
+impl Component for List {
+    fn name(&self) -> &'static str {
+        "List"
+    }
+    fn render(&self, _: &Instance, props: &Props, _: &Children) -> View {
+        let items = props
+            .get("items")
+            .string()
+            .unwrap()
+            .split(',')
+            .filter(|item| !item.is_empty())
+            .map(|item| view! { <li key={item} class={item}>{item}</li> })
+            .collect::<Vec<View>>();
+
+        view! {
+            <ul class={ props.get("class") } onclick={ |_: &mut Props| Prop::Null }>
+                { each items }
+            </ul>
+        }
+    }
+}
+
+#[test]
+fn test_raw_host() {
+    let (server, client, future) = messenger::unbounded_channel::<Value>();
+
+    let transactions: Arc<Mutex<Vec<Transaction>>> = Arc::new(Mutex::new(Vec::new()));
+    let client_transactions = transactions.clone();
+
+    let _ = client.on("virtual_view.transaction", move |t| {
+        client_transactions
+            .lock()
+            .unwrap()
+            .push(from_value(t.clone()).unwrap());
+        None
+    });
+
+    let renderer = Renderer::new_empty(EventManager::new(), server);
+    let root_id = renderer.root_id().clone();
+    let mut snapshots = Vec::new();
+
+    for &(items, class) in &[
+        ("a,b,c,d", "one"),
+        ("d,a,c,b", "one"),
+        ("a,c,e", "two"),
+        ("e,x,a", "two"),
+        ("", "two"),
+        ("b,a", "three"),
+    ] {
+        renderer.render(view! { <{List} items={items} class={class}/> });
+        snapshots.push(renderer.snapshot());
+    }
+
+    renderer.render(view! {
+        <div>
+            { View::new_portal("modal", vec![view! {
+                <p onclick={ |_: &mut Props| Prop::Null }>{"Modal"}</p>
+            }]) }
+        </div>
+    });
+    snapshots.push(renderer.snapshot());
+
+    renderer.unmount();
+    snapshots.push(renderer.snapshot());
+
+    client.close();
+
+    current_thread::run(|_| {
+        let _ = current_thread::spawn(future);
+    });
+
+    let transactions = transactions.lock().unwrap();
+    assert_eq!(transactions.len(), snapshots.len());
+
+    let mut host = RawHost::new();
+
+    for (index, transaction) in transactions.iter().enumerate() {
+        host.apply(transaction).unwrap();
+        assert_eq!(host.view(&root_id), snapshots[index]);
+
+        match index {
+            0 => assert!(host.has_event(&root_id, "onclick")),
+            6 => {
+                assert!(!host.has_event(&root_id, "onclick"));
+                assert!(host.has_event(&format!("{}.0.0.0", root_id), "onclick"));
+                assert_eq!(
+                    host.container("modal"),
+                    Some(vec![RawView::Fragment(vec![RawView::Data {
+                        kind: "p".into(),
+                        key: None,
+                        props: Default::default(),
+                        children: vec![RawView::Text("Modal".into())],
+                    }])])
+                );
+            }
+            7 => {
+                assert_eq!(host.container("modal"), Some(Vec::new()));
+                assert!(host.events().is_empty());
+            }
+            _ => (),
+        }
+    }
+}
+
+#[test]
+fn test_raw_host_errors() {
+    let mut host = RawHost::new();
+    let mut transaction = Transaction::new();
+
+    transaction.replace(".0.1", RawView::Text("a".into()), RawView::Text("b".into()));
+
+    assert_eq!(
+        host.apply(&transaction),
+        Err(ApplyError::NotFound(".0.1".into()))
+    );
+
+    let mut transaction = Transaction::new();
+    transaction.mount(".0", RawView::Text("a".into()));
+    transaction.insert(".0", ".0.0", 0, RawView::Text("b".into()));
+
+    assert_eq!(
+        host.apply(&transaction),
+        Err(ApplyError::NotData(".0".into()))
+    );
+}
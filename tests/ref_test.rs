@@ -0,0 +1,101 @@
+#[macro_use]
+extern crate serde_json;
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use serde_json::Value;
+use virtual_view::{Children, Component, Instance, Props, Ref, TestRenderer, View};
+
+type Refs = Arc<Mutex<Vec<Ref>>>;
+
+struct Form(Refs);
+
+impl Component for Form {
+    fn name(&self) -> &'static str {
+        "Form"
+    }
+    fn render(&self, instance: &Instance, props: &Props, _: &Children) -> View {
+        let input_ref = instance.use_ref();
+        self.0.lock().unwrap().push(input_ref.clone());
+
+        if props.get("hidden").is_true() {
+            view! { <div/> }
+        } else {
+            view! {
+                <div>
+                    <input ref={ input_ref }/>
+                </div>
+            }
+        }
+    }
+}
+
+#[test]
+fn test_ref() {
+    let refs: Refs = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(view! { <{Form(refs.clone())}/> });
+    let input_id = format!("{}.0", test_renderer.root_id());
+
+    let input_ref = refs.lock().unwrap()[0].clone();
+    assert_eq!(input_ref.id(), Some(input_id.clone()));
+
+    // refs can be read from other threads
+    let thread_ref = input_ref.clone();
+    assert_eq!(
+        thread::spawn(move || thread_ref.id()).join().unwrap(),
+        Some(input_id.clone())
+    );
+
+    // the ref is not sent to the host
+    match test_renderer.view(&input_id) {
+        Some(virtual_view::RawView::Data { props, .. }) => assert!(props.is_empty()),
+        view => panic!("expected a data view, got {:?}", view),
+    }
+
+    test_renderer.render(view! { <{Form(refs.clone())}/> });
+    assert!(input_ref.is_mounted());
+
+    test_renderer.render(view! { <{Form(refs.clone())} hidden=true/> });
+    assert!(!input_ref.is_mounted());
+    assert!(!input_ref.call("focus", json!([]), |_| ()));
+
+    // every render used the same ref
+    let refs = refs.lock().unwrap();
+    assert_eq!(refs.len(), 3);
+    assert_eq!(refs[2].id(), None);
+}
+
+#[test]
+fn test_ref_call() {
+    let refs: Refs = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(view! { <{Form(refs.clone())}/> });
+    let input_id = format!("{}.0", test_renderer.root_id());
+
+    let responses: Arc<Mutex<Vec<Value>>> = Arc::new(Mutex::new(Vec::new()));
+    let ref_responses = responses.clone();
+
+    let input_ref = refs.lock().unwrap()[0].clone();
+    assert!(
+        input_ref.call("measure", json!(["width"]), move |response| {
+            ref_responses.lock().unwrap().push(response);
+        })
+    );
+
+    assert_eq!(
+        test_renderer.messages(),
+        vec![(
+            "virtual_view.ref_call".into(),
+            json!({
+                "id": input_id,
+                "method": "measure",
+                "args": ["width"],
+                "component_id": test_renderer.root_id(),
+            }),
+        )]
+    );
+    assert!(test_renderer.respond("virtual_view.ref_call", json!({ "width": 100 })));
+    assert_eq!(*responses.lock().unwrap(), vec![json!({ "width": 100 })]);
+}
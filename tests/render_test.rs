@@ -0,0 +1,116 @@
+extern crate messenger;
+extern crate serde_json;
+extern crate tokio;
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+
+use serde_json::{from_value, Value};
+use tokio::executor::current_thread;
+use virtual_view::{
+    Children, Component, EventManager, Instance, Patch, Prop, Props, RawView, Renderer,
+    Transaction, View,
+};
+
+struct Counter;
+
+impl Component for Counter {
+    fn name(&self) -> &'static str {
+        "Counter"
+    }
+    fn initial_state(&self, _: &Props) -> Props {
+        props! {
+            "count": 0,
+        }
+    }
+    fn render(&self, instance: &Instance, props: &Props, _: &Children) -> View {
+        view! {
+            <div class="Counter">
+                <p>{ format!("{} {}", props.get("label"), instance.state.get("count")) }</p>
+                <button onclick={ block {
+                    let updater = instance.updater.clone();
+                    move |_: &mut Props| {
+                        updater.set_state(|current| {
+                            let mut next = current.clone();
+                            next.update("count", |count| {
+                                if let Some(c) = count.number() {
+                                    *count = (c + 1.0).into();
+                                }
+                            });
+                            next
+                        });
+                        Prop::Null
+                    }
+                } }/>
+            </div>
+        }
+    }
+}
+
+fn text(root_id: &str, transaction: &Transaction) -> Option<RawView> {
+    match transaction.patches().get(&format!("{}.0.0", root_id)) {
+        Some(patches) => match &patches[0] {
+            &Patch::Replace(_, ref next) => Some(next.clone()),
+            _ => None,
+        },
+        None => None,
+    }
+}
+
+#[test]
+fn test_render() {
+    let (server, client, future) = messenger::unbounded_channel::<Value>();
+
+    let transactions: Arc<Mutex<Vec<Transaction>>> = Arc::new(Mutex::new(Vec::new()));
+    let client_transactions = transactions.clone();
+
+    let _ = client.on("virtual_view.transaction", move |t| {
+        client_transactions
+            .lock()
+            .unwrap()
+            .push(from_value(t.clone()).unwrap());
+        None
+    });
+
+    let renderer = Renderer::new_empty(EventManager::new(), server);
+    let root_id = renderer.root_id().clone();
+
+    renderer.render(view! { <{Counter} label="a"/> });
+    renderer.dispatch(&format!("{}.1", root_id), &mut props! { "name": "onclick" });
+    renderer.render(view! { <{Counter} label="b"/> });
+    renderer.render(view! { <{Counter} label="b"/> });
+    renderer.render(view! { <p>{"Done"}</p> });
+    client.close();
+
+    current_thread::run(|_| {
+        let _ = current_thread::spawn(future);
+    });
+
+    let transactions = transactions.lock().unwrap();
+    assert_eq!(transactions.len(), 4);
+
+    assert!(transactions[0].patches()[&root_id][0].is_mount());
+    assert_eq!(
+        text(&root_id, &transactions[1]),
+        Some(RawView::Text("a 1".into()))
+    );
+
+    let patches = transactions[2].patches();
+    assert_eq!(patches.len(), 1);
+    assert_eq!(
+        text(&root_id, &transactions[2]),
+        Some(RawView::Text("b 1".into()))
+    );
+    assert!(transactions[2].events().is_empty());
+    assert!(transactions[2].removes().is_empty());
+
+    match &transactions[3].patches()[&root_id][0] {
+        &Patch::Replace(ref prev, ref next) => {
+            assert_eq!(prev.kind().map(String::as_str), Some("div"));
+            assert_eq!(next.kind().map(String::as_str), Some("p"));
+        }
+        patch => panic!("expected a replace patch, found {:?}", patch),
+    }
+    assert!(!transactions[3].events()[&format!("{}.1", root_id)]["onclick"]);
+}
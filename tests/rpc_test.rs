@@ -0,0 +1,110 @@
+extern crate futures;
+#[macro_use]
+extern crate serde_json;
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::Future;
+use virtual_view::{Children, Component, Instance, Props, Request, RpcError, TestRenderer, View};
+
+type Requests = Arc<Mutex<Vec<Request>>>;
+
+struct Loader(Requests, u64);
+
+impl Component for Loader {
+    fn name(&self) -> &'static str {
+        "Loader"
+    }
+    fn did_mount(&self, instance: &Instance) {
+        let mut requests = self.0.lock().unwrap();
+
+        requests.push(instance.request("load", json!({ "page": 1 })));
+        requests.push(instance.request("load", json!({ "page": 2 })));
+        requests.push(instance.request_timeout(
+            "never",
+            json!({}),
+            Some(Duration::from_millis(self.1)),
+        ));
+    }
+    fn render(&self, _: &Instance, _: &Props, _: &Children) -> View {
+        view! { <div/> }
+    }
+}
+
+#[test]
+fn test_rpc() {
+    let requests: Requests = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(view! { <{Loader(requests.clone(), 10)}/> });
+
+    assert!(test_renderer.respond("load", json!({ "items": ["a", "b"] })));
+    assert!(test_renderer.respond("load", json!({ "error": "no such page" })));
+    test_renderer.advance(Duration::from_millis(10));
+
+    let mut requests = requests.lock().unwrap().drain(..).collect::<Vec<_>>();
+    let never = requests.pop().unwrap();
+    let page_2 = requests.pop().unwrap();
+    let page_1 = requests.pop().unwrap();
+
+    assert_eq!(page_1.wait(), Ok(json!({ "items": ["a", "b"] })));
+    assert_eq!(page_2.wait(), Err(RpcError::Host(json!("no such page"))));
+    assert_eq!(never.wait(), Err(RpcError::Timeout));
+}
+
+#[test]
+fn test_rpc_clears_timeouts() {
+    let requests: Requests = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(view! { <div/> });
+    test_renderer
+        .renderer()
+        .set_request_timeout(Some(Duration::from_secs(30)));
+    test_renderer.render(view! { <{Loader(requests.clone(), 10)}/> });
+    assert_eq!(test_renderer.clock().pending(), 3);
+
+    assert!(test_renderer.respond("load", json!({ "items": [] })));
+    assert!(test_renderer.respond("load", json!({ "items": [] })));
+
+    // the answered requests cleared their timeouts
+    assert_eq!(test_renderer.clock().pending(), 1);
+
+    let never = requests.lock().unwrap().pop().unwrap();
+    test_renderer.advance(Duration::from_millis(10));
+    assert_eq!(never.wait().unwrap_err(), RpcError::Timeout);
+    assert_eq!(test_renderer.clock().pending(), 0);
+}
+
+#[test]
+fn test_rpc_cancel_on_unmount() {
+    let requests: Requests = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(view! { <{Loader(requests.clone(), 60000)}/> });
+
+    // only the request sent with a timeout schedules one
+    assert_eq!(test_renderer.clock().pending(), 1);
+    test_renderer.unmount();
+    assert_eq!(test_renderer.clock().pending(), 0);
+
+    for request in requests.lock().unwrap().drain(..) {
+        let error = request.wait().unwrap_err();
+        assert!(error.is_cancelled(), "expected cancelled, got {:?}", error);
+    }
+}
+
+#[test]
+fn test_rpc_non_object_response() {
+    let requests: Requests = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(view! { <{Loader(requests.clone(), 60000)}/> });
+
+    assert!(test_renderer.respond("load", json!("page one")));
+    assert!(test_renderer.respond("load", json!([1, 2])));
+
+    let mut requests = requests.lock().unwrap().drain(..).collect::<Vec<_>>();
+    requests.pop();
+    let page_2 = requests.pop().unwrap();
+    let page_1 = requests.pop().unwrap();
+
+    // responses are not merged into an object
+    assert_eq!(page_1.wait().unwrap(), json!("page one"));
+    assert_eq!(page_2.wait().unwrap(), json!([1, 2]));
+}
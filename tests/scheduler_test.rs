@@ -0,0 +1,229 @@
+extern crate messenger;
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, ThreadId};
+use std::time::Duration;
+
+use virtual_view::{
+    Children, Component, EventManager, Instance, ManualClock, Props, RawView, Renderer, Scheduler,
+    TestRenderer, ThreadScheduler, View,
+};
+
+struct Clock;
+
+impl Component for Clock {
+    fn name(&self) -> &'static str {
+        "Clock"
+    }
+    fn initial_state(&self, _: &Props) -> Props {
+        props! {
+            "ticks": 0,
+            "done": 0,
+            "frame": 0,
+        }
+    }
+    fn did_mount(&self, instance: &Instance) {
+        let updater = instance.updater.clone();
+
+        instance.set_interval(Duration::from_millis(1000), {
+            let updater = updater.clone();
+            move || {
+                updater.set_state(|state| {
+                    let mut state = state.clone();
+                    let ticks = state.get("ticks").number().unwrap() + 1.0;
+                    state.set("ticks", ticks);
+                    state
+                })
+            }
+        });
+        instance.set_timeout(Duration::from_millis(500), {
+            let updater = updater.clone();
+            move || {
+                updater.set_state(|state| {
+                    let mut state = state.clone();
+                    state.set("done", 1);
+                    state
+                })
+            }
+        });
+        instance.request_frame(move || {
+            updater.set_state(|state| {
+                let mut state = state.clone();
+                state.set("frame", 1);
+                state
+            })
+        });
+
+        let cleared = instance.set_timeout(Duration::from_millis(100), {
+            let updater = instance.updater.clone();
+            move || updater.set_state(|_| panic!("cleared timeout ran"))
+        });
+        instance.clear_timer(cleared);
+    }
+    fn render(&self, instance: &Instance, _: &Props, _: &Children) -> View {
+        let state = instance.state();
+
+        view! {
+            <p>{ format!(
+                "ticks {} done {} frame {}",
+                state.get("ticks"),
+                state.get("done"),
+                state.get("frame")
+            ) }</p>
+        }
+    }
+}
+
+#[test]
+fn test_scheduler_timers() {
+    let test_renderer = TestRenderer::new(view! { <div><{Clock}/></div> });
+    let text_id = format!("{}.0.0", test_renderer.root_id());
+    let clock = test_renderer.clock();
+
+    let text = |text: &str| Some(RawView::Text(text.into()));
+
+    assert_eq!(test_renderer.view(&text_id), text("ticks 0 done 0 frame 0"));
+
+    test_renderer.advance(Duration::from_millis(499));
+    assert_eq!(test_renderer.view(&text_id), text("ticks 0 done 0 frame 0"));
+
+    test_renderer.advance(Duration::from_millis(1));
+    assert_eq!(test_renderer.view(&text_id), text("ticks 0 done 1 frame 0"));
+
+    test_renderer.frame();
+    assert_eq!(test_renderer.view(&text_id), text("ticks 0 done 1 frame 1"));
+
+    test_renderer.advance(Duration::from_millis(2500));
+    assert_eq!(test_renderer.view(&text_id), text("ticks 3 done 1 frame 1"));
+    assert_eq!(clock.now(), Duration::from_millis(3000));
+
+    // only the interval is left and it is cleared with the component
+    assert_eq!(clock.pending(), 1);
+    test_renderer.render(view! { <div/> });
+    assert_eq!(clock.pending(), 0);
+
+    test_renderer.advance(Duration::from_millis(5000));
+}
+
+#[test]
+fn test_manual_clock() {
+    let clock = ManualClock::new();
+    let log: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+
+    let interval_log = log.clone();
+    let interval_clock = clock.clone();
+    clock.set_interval(
+        Duration::from_millis(10),
+        Box::new(move || {
+            let now = interval_clock.now();
+            interval_log
+                .lock()
+                .unwrap()
+                .push(format!("interval {:?}", now));
+        }),
+    );
+
+    let timeout_log = log.clone();
+    let timeout_clock = clock.clone();
+    clock.set_timeout(
+        Duration::from_millis(15),
+        Box::new(move || {
+            timeout_log.lock().unwrap().push("timeout".into());
+
+            // timers scheduled while advancing run if they are due in time
+            let nested_log = timeout_log.clone();
+            timeout_clock.set_timeout(
+                Duration::from_millis(1),
+                Box::new(move || nested_log.lock().unwrap().push("nested".into())),
+            );
+        }),
+    );
+
+    let frame_log = log.clone();
+    let frame = clock.request_frame(Box::new(move || {
+        frame_log.lock().unwrap().push("frame".into())
+    }));
+    clock.clear(frame);
+
+    clock.advance(Duration::from_millis(25));
+    clock.frame();
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec!["interval 10ms", "timeout", "nested", "interval 20ms"]
+    );
+    assert_eq!(clock.pending(), 1);
+}
+
+struct Delayed(Arc<Mutex<Vec<ThreadId>>>);
+
+impl Component for Delayed {
+    fn name(&self) -> &'static str {
+        "Delayed"
+    }
+    fn did_mount(&self, instance: &Instance) {
+        let threads = self.0.clone();
+
+        instance.set_timeout(Duration::from_millis(1), move || {
+            threads.lock().unwrap().push(thread::current().id())
+        });
+    }
+    fn render(&self, _: &Instance, _: &Props, _: &Children) -> View {
+        view! { <div/> }
+    }
+}
+
+#[test]
+fn test_thread_scheduler_runs_timers_on_renderer_thread() {
+    let (server, _, _) = messenger::unbounded_channel();
+    let threads = Arc::new(Mutex::new(Vec::new()));
+    let (sender, receiver) = mpsc::channel();
+    let sender = Mutex::new(sender);
+
+    let renderer = Renderer::new_empty(EventManager::new(), server);
+    renderer.set_waker(move || {
+        let _ = sender.lock().unwrap().send(());
+    });
+    renderer.mount(view! { <{Delayed(threads.clone())}/> });
+
+    // the scheduler only queues the timer and wakes this thread
+    receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert!(threads.lock().unwrap().is_empty());
+
+    renderer.process();
+    assert_eq!(*threads.lock().unwrap(), vec![thread::current().id()]);
+}
+
+#[test]
+fn test_thread_scheduler() {
+    let scheduler = ThreadScheduler::new();
+    let (sender, receiver) = mpsc::channel();
+    let cleared_sender = Mutex::new(sender.clone());
+    let sender = Mutex::new(sender);
+
+    let cleared = scheduler.set_timeout(
+        Duration::from_millis(1),
+        Box::new(move || {
+            let _ = cleared_sender.lock().unwrap().send("cleared");
+        }),
+    );
+    scheduler.clear(cleared);
+
+    let interval = scheduler.set_interval(
+        Duration::from_millis(1),
+        Box::new(move || {
+            let _ = sender.lock().unwrap().send("interval");
+        }),
+    );
+
+    for _ in 0..3 {
+        assert_eq!(
+            receiver.recv_timeout(Duration::from_secs(5)).unwrap(),
+            "interval"
+        );
+    }
+    scheduler.clear(interval);
+}
@@ -0,0 +1,211 @@
+extern crate futures;
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+
+use futures::sync::oneshot;
+use futures::Future;
+use virtual_view::{
+    Children, Component, Instance, Props, RawView, RenderError, TestRenderer, View,
+};
+
+type Senders = Arc<Mutex<Vec<oneshot::Sender<String>>>>;
+type Futures = Arc<Mutex<Vec<Box<dyn Future<Item = (), Error = ()>>>>>;
+
+struct User {
+    senders: Senders,
+    futures: Futures,
+    load: bool,
+}
+
+impl Component for User {
+    fn name(&self) -> &'static str {
+        "User"
+    }
+    fn did_mount(&self, instance: &Instance) {
+        let (sender, receiver) = oneshot::channel::<String>();
+        self.senders.lock().unwrap().push(sender);
+
+        let future = receiver.map(|name| {
+            move |state: &Props| {
+                let mut state = state.clone();
+                state.set("name", name.clone());
+                state
+            }
+        });
+
+        self.futures.lock().unwrap().push(if self.load {
+            Box::new(instance.load(future.map_err(|_| "cancelled")))
+        } else {
+            Box::new(instance.set_state_async(future).map_err(|_| ()))
+        });
+    }
+    fn render(&self, instance: &Instance, _: &Props, _: &Children) -> View {
+        let text = if instance.is_loading() {
+            "loading".to_owned()
+        } else if let Some(error) = instance.load_error() {
+            format!("error {}", error)
+        } else {
+            format!("name {}", instance.state.get("name"))
+        };
+
+        view! {
+            <p>{ text }</p>
+        }
+    }
+}
+
+fn app(senders: &Senders, futures: &Futures, load: bool) -> View {
+    view! {
+        <div>
+            <{User {
+                senders: senders.clone(),
+                futures: futures.clone(),
+                load: load,
+            }}/>
+        </div>
+    }
+}
+
+fn wait(futures: &Futures) {
+    for future in futures.lock().unwrap().drain(..) {
+        future.wait().unwrap();
+    }
+}
+
+#[test]
+fn test_load() {
+    let senders: Senders = Arc::new(Mutex::new(Vec::new()));
+    let futures: Futures = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(app(&senders, &futures, true));
+    let text_id = format!("{}.0.0", test_renderer.root_id());
+
+    assert_eq!(
+        test_renderer.view(&text_id),
+        Some(RawView::Text("loading".into()))
+    );
+
+    let sender = senders.lock().unwrap().pop().unwrap();
+    sender.send("Ann".into()).unwrap();
+    wait(&futures);
+
+    assert_eq!(
+        test_renderer.view(&text_id),
+        Some(RawView::Text("name Ann".into()))
+    );
+}
+
+#[test]
+fn test_load_error() {
+    let senders: Senders = Arc::new(Mutex::new(Vec::new()));
+    let futures: Futures = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(app(&senders, &futures, true));
+    let text_id = format!("{}.0.0", test_renderer.root_id());
+
+    senders.lock().unwrap().clear();
+    wait(&futures);
+
+    assert_eq!(
+        test_renderer.view(&text_id),
+        Some(RawView::Text("error cancelled".into()))
+    );
+}
+
+#[test]
+fn test_set_state_async_after_unmount() {
+    let senders: Senders = Arc::new(Mutex::new(Vec::new()));
+    let futures: Futures = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(app(&senders, &futures, false));
+    let text_id = format!("{}.0.0", test_renderer.root_id());
+
+    assert_eq!(
+        test_renderer.view(&text_id),
+        Some(RawView::Text("name null".into()))
+    );
+
+    test_renderer.render(view! { <div/> });
+    test_renderer.take_transactions();
+
+    // the future resolves without waiting for its state once the component unmounted
+    wait(&futures);
+    assert!(senders
+        .lock()
+        .unwrap()
+        .pop()
+        .unwrap()
+        .send("Ann".into())
+        .is_err());
+    assert!(test_renderer.take_transactions().is_empty());
+}
+
+struct Thrower;
+
+impl Component for Thrower {
+    fn name(&self) -> &'static str {
+        "Thrower"
+    }
+    fn render(&self, _: &Instance, props: &Props, _: &Children) -> View {
+        if props.get("fail").is_true() {
+            panic!("failed to render");
+        }
+        view! { <span/> }
+    }
+}
+
+struct Boundary {
+    senders: Senders,
+    futures: Futures,
+}
+
+impl Component for Boundary {
+    fn name(&self) -> &'static str {
+        "Boundary"
+    }
+    fn catch_error(&self, _: &Instance, _: &RenderError) -> Option<View> {
+        Some(view! { <p>{"fallback"}</p> })
+    }
+    fn render(&self, _: &Instance, props: &Props, _: &Children) -> View {
+        view! {
+            <div>
+                <{User {
+                    senders: self.senders.clone(),
+                    futures: self.futures.clone(),
+                    load: false,
+                }}/>
+                <{Thrower} fail={ props.get("fail").is_true() }/>
+            </div>
+        }
+    }
+}
+
+fn boundary(senders: &Senders, futures: &Futures, fail: bool) -> View {
+    view! {
+        <{Boundary {
+            senders: senders.clone(),
+            futures: futures.clone(),
+        }} fail={ fail }/>
+    }
+}
+
+#[test]
+fn test_set_state_async_after_recover() {
+    let senders: Senders = Arc::new(Mutex::new(Vec::new()));
+    let futures: Futures = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(boundary(&senders, &futures, false));
+    let text_id = format!("{}.0.0", test_renderer.root_id());
+
+    // the boundary removes the user without unmounting it, then mounts a new user in its place
+    test_renderer.render(boundary(&senders, &futures, true));
+    test_renderer.render(boundary(&senders, &futures, false));
+    assert_eq!(senders.lock().unwrap().len(), 2);
+
+    let sender = senders.lock().unwrap().remove(0);
+    sender.send("Ann".into()).unwrap();
+    futures.lock().unwrap().remove(0).wait().unwrap();
+
+    assert_eq!(
+        test_renderer.view(&text_id),
+        Some(RawView::Text("name null".into()))
+    );
+}
@@ -0,0 +1,98 @@
+extern crate messenger;
+extern crate serde_json;
+extern crate tokio;
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+
+use serde_json::{from_value, Value};
+use tokio::executor::current_thread;
+use virtual_view::{
+    Children, Component, EventManager, Instance, Patch, Prop, Props, RawView, Renderer,
+    Transaction, View,
+};
+
+struct Toggle;
+
+impl Component for Toggle {
+    fn name(&self) -> &'static str {
+        "Toggle"
+    }
+    fn initial_state(&self, _: &Props) -> Props {
+        props! {
+            "on": false,
+        }
+    }
+    fn render(&self, instance: &Instance, _: &Props, _: &Children) -> View {
+        view! {
+            <div class="Toggle">
+                <p>{ if instance.state.get("on").is_true() { "On" } else { "Off" } }</p>
+                <button onclick={ block {
+                    let updater = instance.updater.clone();
+                    move |_: &mut Props| {
+                        updater.set_state(|_| props! { "on": true });
+                        Prop::Null
+                    }
+                } }/>
+            </div>
+        }
+    }
+}
+
+#[test]
+fn test_snapshot() {
+    let (server, client, future) = messenger::unbounded_channel::<Value>();
+
+    let transactions: Arc<Mutex<Vec<Transaction>>> = Arc::new(Mutex::new(Vec::new()));
+    let client_transactions = transactions.clone();
+
+    let _ = client.on("virtual_view.transaction", move |t| {
+        client_transactions
+            .lock()
+            .unwrap()
+            .push(from_value(t.clone()).unwrap());
+        None
+    });
+
+    let renderer = Renderer::new_empty(EventManager::new(), server);
+    let root_id = renderer.root_id().clone();
+
+    assert_eq!(renderer.snapshot(), None);
+
+    renderer.mount(view! { <{Toggle}/> });
+    let mounted = renderer.snapshot().unwrap();
+
+    assert_eq!(
+        renderer.snapshot_at(&format!("{}.0.0", root_id)),
+        Some(RawView::Text("Off".into()))
+    );
+
+    renderer.dispatch(&format!("{}.1", root_id), &mut props! { "name": "onclick" });
+
+    assert_eq!(
+        renderer.snapshot_at(&format!("{}.0", root_id)),
+        Some(RawView::Data {
+            kind: "p".into(),
+            key: None,
+            props: Default::default(),
+            children: vec![RawView::Text("On".into())],
+        })
+    );
+    assert_eq!(renderer.snapshot_at(&format!("{}.2", root_id)), None);
+
+    renderer.unmount();
+    assert_eq!(renderer.snapshot(), None);
+
+    client.close();
+
+    current_thread::run(|_| {
+        let _ = current_thread::spawn(future);
+    });
+
+    let transactions = transactions.lock().unwrap();
+    assert_eq!(
+        transactions[0].patches()[&root_id],
+        vec![Patch::Mount(mounted)]
+    );
+}
@@ -0,0 +1,113 @@
+#[macro_use]
+extern crate serde_json;
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+
+use serde_json::Value;
+use virtual_view::{Children, Component, Instance, Props, RawView, TestRenderer, View};
+
+type Log = Arc<Mutex<Vec<Value>>>;
+
+struct Window(Log);
+
+impl Component for Window {
+    fn name(&self) -> &'static str {
+        "Window"
+    }
+    fn initial_state(&self, _: &Props) -> Props {
+        props! {
+            "width": 0,
+        }
+    }
+    fn did_mount(&self, instance: &Instance) {
+        let log = self.0.clone();
+        let updater = instance.updater.clone();
+
+        instance.subscribe("resize", move |data| {
+            log.lock().unwrap().push(data.clone());
+
+            let width = data.get("width").and_then(Value::as_f64).unwrap_or(0.0);
+            updater.set_state(move |_| {
+                props! {
+                    "width": width,
+                }
+            });
+        });
+    }
+    fn render(&self, instance: &Instance, _: &Props, _: &Children) -> View {
+        view! {
+            <p>{ format!("width {}", instance.state.get("width")) }</p>
+        }
+    }
+}
+
+fn app(log: &Log, show: bool) -> View {
+    if show {
+        view! {
+            <div>
+                <{Window(log.clone())}/>
+                <{Window(log.clone())}/>
+            </div>
+        }
+    } else {
+        view! { <div/> }
+    }
+}
+
+#[test]
+fn test_subscribe() {
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(app(&log, true));
+    let text_id = format!("{}.0.0", test_renderer.root_id());
+
+    test_renderer
+        .renderer()
+        .broadcast("resize", &json!({ "width": 100 }));
+
+    assert_eq!(log.lock().unwrap().len(), 2);
+    assert_eq!(
+        test_renderer.view(&text_id),
+        Some(RawView::Text("width 100".into()))
+    );
+    // both windows updated in one transaction
+    assert_eq!(test_renderer.take_transactions().len(), 2);
+
+    test_renderer.renderer().broadcast("scroll", &json!({}));
+    assert_eq!(log.lock().unwrap().len(), 2);
+
+    test_renderer.render(app(&log, false));
+    test_renderer
+        .renderer()
+        .broadcast("resize", &json!({ "width": 200 }));
+
+    assert_eq!(log.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn test_subscribe_host_message() {
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(app(&log, true));
+
+    test_renderer.message("resize", json!({ "width": 50 }));
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![json!({ "width": 50 }), json!({ "width": 50 })]
+    );
+}
+
+#[test]
+fn test_subscribe_after_remount() {
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(app(&log, true));
+
+    test_renderer.unmount();
+    test_renderer.message("resize", json!({ "width": 50 }));
+    assert!(log.lock().unwrap().is_empty());
+
+    test_renderer.render(app(&log, true));
+    test_renderer.message("resize", json!({ "width": 100 }));
+    assert_eq!(log.lock().unwrap().len(), 2);
+}
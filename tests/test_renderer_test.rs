@@ -0,0 +1,151 @@
+extern crate futures;
+#[macro_use]
+extern crate serde_json;
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+
+use futures::Future;
+use serde_json::Value;
+use virtual_view::{
+    Children, Component, Instance, Prop, Props, RawView, Ref, Request, TestRenderer, View,
+};
+
+struct Counter;
+
+impl Component for Counter {
+    fn name(&self) -> &'static str {
+        "Counter"
+    }
+    fn initial_state(&self, _: &Props) -> Props {
+        props! {
+            "count": 0,
+        }
+    }
+    fn render(&self, instance: &Instance, _: &Props, _: &Children) -> View {
+        view! {
+            <div class="Counter">
+                <p class="count">{ format!("Count {}", instance.state.get("count")) }</p>
+                <button onclick={ block {
+                    let updater = instance.updater.clone();
+                    move |e: &mut Props| {
+                        let amount = e.get("amount").number().unwrap_or(1.0);
+
+                        updater.set_state(move |current| {
+                            let mut next = current.clone();
+                            next.update("count", |count| {
+                                if let Some(c) = count.number() {
+                                    *count = (c + amount).into();
+                                }
+                            });
+                            next
+                        });
+                        e.set("handled", true);
+                        Prop::Null
+                    }
+                } }/>
+            </div>
+        }
+    }
+}
+
+#[test]
+fn test_test_renderer() {
+    let test_renderer = TestRenderer::new(view! { <{Counter}/> });
+    let root_id = test_renderer.root_id().clone();
+
+    assert_eq!(test_renderer.transactions().len(), 1);
+    assert_eq!(
+        test_renderer.find_by_prop("class", "Counter")[0].0,
+        root_id.clone()
+    );
+
+    let buttons = test_renderer.find_by_kind("button");
+    assert_eq!(buttons.len(), 1);
+
+    let button_id = buttons[0].0.clone();
+    assert!(test_renderer.has_event(&button_id, "onclick"));
+
+    let event = test_renderer.simulate(&button_id, "onclick", props! { "amount": 2 });
+    assert!(event.get("handled").is_true());
+
+    let event = test_renderer.simulate(&button_id, "onclick", Props::new());
+    assert!(event.get("handled").is_true());
+
+    assert_eq!(
+        test_renderer.find_by_prop("class", "count")[0].1.children(),
+        Some(&vec![RawView::Text("Count 3".into())])
+    );
+    assert_eq!(test_renderer.take_transactions().len(), 3);
+
+    test_renderer.unmount();
+    assert_eq!(test_renderer.tree(), None);
+    assert_eq!(test_renderer.take_transactions().len(), 1);
+}
+
+type Log = Arc<Mutex<Vec<Value>>>;
+
+struct Remote {
+    log: Log,
+    requests: Arc<Mutex<Vec<Request>>>,
+    refs: Arc<Mutex<Vec<Ref>>>,
+}
+
+impl Component for Remote {
+    fn name(&self) -> &'static str {
+        "Remote"
+    }
+    fn did_mount(&self, instance: &Instance) {
+        let log = self.log.clone();
+        instance.subscribe("resize", move |data| log.lock().unwrap().push(data.clone()));
+
+        self.requests
+            .lock()
+            .unwrap()
+            .push(instance.request("load", json!({ "page": 1 })));
+    }
+    fn render(&self, instance: &Instance, _: &Props, _: &Children) -> View {
+        let input_ref = instance.use_ref();
+        self.refs.lock().unwrap().push(input_ref.clone());
+
+        view! { <input ref={ input_ref }/> }
+    }
+}
+
+#[test]
+fn test_test_renderer_messages() {
+    let log: Log = Arc::new(Mutex::new(Vec::new()));
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let refs = Arc::new(Mutex::new(Vec::new()));
+    let test_renderer = TestRenderer::new(view! {
+        <{Remote { log: log.clone(), requests: requests.clone(), refs: refs.clone() }}/>
+    });
+
+    let ref_log = log.clone();
+    let input_ref = refs.lock().unwrap()[0].clone();
+    assert!(input_ref.call("focus", json!([]), move |response| {
+        ref_log.lock().unwrap().push(response)
+    }));
+
+    let names = test_renderer
+        .messages()
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec!["load", "virtual_view.ref_call"]);
+
+    assert!(test_renderer.respond("virtual_view.ref_call", json!({ "focused": true })));
+    assert!(test_renderer.respond("load", json!({ "items": ["a"] })));
+    assert!(!test_renderer.respond("load", json!({})));
+    assert!(test_renderer.messages().is_empty());
+
+    let request = requests.lock().unwrap().pop().unwrap();
+    assert_eq!(request.wait().unwrap(), json!({ "items": ["a"] }));
+
+    test_renderer.message("resize", json!({ "width": 100 }));
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![json!({ "focused": true }), json!({ "width": 100 })]
+    );
+}
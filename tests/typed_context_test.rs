@@ -0,0 +1,162 @@
+#[macro_use]
+extern crate virtual_view;
+
+use std::sync::{Arc, Mutex};
+
+use virtual_view::{
+    memo, render_to_string, Children, Component, Instance, Props, RawView, TestRenderer,
+    TypedContext, View,
+};
+
+struct Store {
+    name: &'static str,
+    count: Mutex<usize>,
+}
+
+#[derive(PartialEq)]
+struct Theme(String);
+
+struct App(Arc<Store>);
+
+impl Component for App {
+    fn name(&self) -> &'static str {
+        "App"
+    }
+    fn typed_context(&self, _: &Props) -> TypedContext {
+        let mut context = TypedContext::new();
+        context.insert_arc(self.0.clone());
+        context
+    }
+    fn render(&self, _: &Instance, props: &Props, _: &Children) -> View {
+        view! {
+            <div>
+                <{Themed} theme={ props.get("theme") }/>
+            </div>
+        }
+    }
+}
+
+struct Themed;
+
+impl Component for Themed {
+    fn name(&self) -> &'static str {
+        "Themed"
+    }
+    fn typed_context(&self, props: &Props) -> TypedContext {
+        let mut context = TypedContext::new();
+        context.insert_eq(Theme(props.get("theme").to_string()));
+        context
+    }
+    fn render(&self, _: &Instance, _: &Props, _: &Children) -> View {
+        view! {
+            <{memo(Middle)}/>
+        }
+    }
+}
+
+struct Middle;
+
+impl Component for Middle {
+    fn name(&self) -> &'static str {
+        "Middle"
+    }
+    fn render(&self, _: &Instance, _: &Props, _: &Children) -> View {
+        view! {
+            <{Consumer}/>
+        }
+    }
+}
+
+struct Consumer;
+
+impl Component for Consumer {
+    fn name(&self) -> &'static str {
+        "Consumer"
+    }
+    fn render(&self, instance: &Instance, _: &Props, _: &Children) -> View {
+        let store = instance.context_get::<Store>().unwrap();
+        let theme = instance.context_get::<Theme>().unwrap();
+
+        *store.count.lock().unwrap() += 1;
+
+        assert!(instance.context_get::<String>().is_none());
+
+        view! {
+            <p>{ format!("{} {}", store.name, theme.0) }</p>
+        }
+    }
+}
+
+fn store() -> Arc<Store> {
+    Arc::new(Store {
+        name: "store",
+        count: Mutex::new(0),
+    })
+}
+
+#[test]
+fn test_typed_context() {
+    let store = store();
+    let test_renderer = TestRenderer::new(view! { <{App(store.clone())} theme="dark"/> });
+    let text_id = format!("{}.0.0", test_renderer.root_id());
+
+    assert_eq!(
+        test_renderer.view(&text_id),
+        Some(RawView::Text("store dark".into()))
+    );
+    assert_eq!(*store.count.lock().unwrap(), 1);
+
+    test_renderer.render(view! { <{App(store.clone())} theme="light"/> });
+
+    assert_eq!(
+        test_renderer.view(&text_id),
+        Some(RawView::Text("store light".into()))
+    );
+    assert_eq!(*store.count.lock().unwrap(), 2);
+
+    // the theme is created again but equal, so the consumer is not updated
+    test_renderer.render(view! { <{App(store.clone())} theme="light"/> });
+    assert_eq!(*store.count.lock().unwrap(), 2);
+}
+
+#[test]
+fn test_typed_context_html() {
+    assert_eq!(
+        render_to_string(view! { <{App(store())} theme="dark"/> }),
+        "<div><p>store dark</p></div>"
+    );
+}
+
+#[test]
+fn test_typed_context_map() {
+    let mut context = TypedContext::new();
+    assert!(context.is_empty());
+
+    context.insert(Theme("light".into()));
+    context.insert(10usize);
+
+    let mut next_context = context.clone();
+    assert_eq!(context, next_context);
+
+    next_context.insert(Theme("dark".into()));
+    assert!(context != next_context);
+    assert_eq!(next_context.len(), 2);
+    assert_eq!(next_context.get::<Theme>().unwrap().0, "dark");
+    assert_eq!(*next_context.remove::<usize>().unwrap(), 10);
+    assert!(!next_context.has::<usize>());
+
+    // values inserted with insert_eq are compared by value, others by pointer
+    let mut a = TypedContext::new();
+    let mut b = TypedContext::new();
+    a.insert_eq(Theme("dark".into()));
+    b.insert_eq(Theme("dark".into()));
+    assert_eq!(a, b);
+
+    b.insert_eq(Theme("light".into()));
+    assert!(a != b);
+
+    a.insert(10usize);
+    b = a.clone();
+    b.insert(10usize);
+    assert!(a != b);
+}